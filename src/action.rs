@@ -5,9 +5,21 @@ use itertools::Itertools;
 use serde::de::*;
 use serde::*;
 use triton_vm::isa::instruction::Instruction;
-
+use triton_vm::prelude::BFieldElement;
+use triton_vm::prelude::bfe;
+use triton_vm::prelude::tip5;
+use triton_vm::prelude::twenty_first::math::x_field_element::EXTENSION_DEGREE;
+
+use crate::catchpoint::Catchpoint;
+use crate::command::Location;
+use crate::condition::Condition;
 use crate::mode::Mode;
+use crate::prover::ProofReport;
+use crate::prover::ProvingJob;
+use crate::prover::ProvingPhase;
 use crate::shadow_memory::TopOfStack;
+use crate::watchpoint::AddressRange;
+use crate::watchpoint::Watchpoint;
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub(crate) enum Action {
@@ -25,13 +37,95 @@ pub(crate) enum Action {
     /// Undo the last [`Execute`] action.
     Undo,
 
+    /// Redo the most recently undone [`Execute`] action.
+    Redo,
+
     RecordUndoInfo,
 
+    /// Execution travelled to a different cycle, for example by undoing or by executing in
+    /// reverse. Carries the RAM address most recently accessed as of that cycle.
+    Rewound(BFieldElement),
+
     /// Reset the program state.
     Reset,
 
+    /// Reload the program and its inputs, then run to where execution was before.
+    Reload,
+
     Toggle(Toggle),
 
+    /// Toggle a breakpoint at the instruction under the program cursor.
+    ToggleBreakpoint,
+
+    /// Toggle a breakpoint at the given instruction address.
+    ToggleBreakpointAt(usize),
+
+    /// Execute until the instruction pointer reaches the instruction under the program cursor
+    /// or the current call returns.
+    RunToCursor,
+
+    /// Set a breakpoint at the given location, optionally only stopping if the condition holds.
+    SetBreakpoint(Location, Option<Condition>),
+
+    /// Remove the runtime breakpoint at the given location.
+    DeleteBreakpoint(Location),
+
+    SetWatchpoint(Watchpoint),
+
+    /// Remove all watchpoints overlapping the given address range.
+    DeleteWatchpoints(AddressRange),
+
+    SetCatchpoint(Catchpoint),
+    DeleteCatchpoint(Catchpoint),
+
+    /// Write the execution profile to the given file.
+    ExportProfile(String),
+
+    /// Save the current state as a checkpoint with the given name.
+    SaveCheckpoint(String),
+
+    /// Go to the checkpoint with the given name.
+    RestoreCheckpoint(String),
+
+    DeleteCheckpoint(String),
+
+    /// Write the current VM state to the given file or, if there is none, to a file in the data
+    /// directory. If set, includes the type hints.
+    DumpState(Option<String>, bool),
+
+    /// Prove the run of the halted program in the background, then verify the proof.
+    Prove,
+
+    /// The given prover job started the given phase.
+    ProvingProgress(ProvingJob, ProvingPhase),
+
+    /// The given prover job is done, successfully or not.
+    ProofGenerated(ProvingJob, Box<Result<ProofReport, String>>),
+
+    /// Write the most recently generated proof and its claim to the given directory or, if there
+    /// is none, to the data directory.
+    SaveProof(Option<String>),
+
+    /// Change the public input not yet read by the program.
+    EditPublicInput(InputEdit),
+
+    /// Write the public input not yet read by the program to the given file.
+    SavePublicInput(String),
+
+    /// Execution paused because the next instruction needs more input than there is.
+    RequestInput(InputRequest),
+
+    /// Input entered in response to an [`Action::RequestInput`]. Execution continues as if it
+    /// had been part of the input from the start.
+    EnterInput(Vec<BFieldElement>),
+
+    /// Write the session's entire input, including all entered input, to the given directory
+    /// or, if there is none, to the data directory.
+    ExportInput(Option<String>),
+
+    /// Replay the next action of a session that is replayed stepwise.
+    ReplayNext,
+
     HideHelpScreen,
 
     Mode(Mode),
@@ -40,31 +134,122 @@ pub(crate) enum Action {
 }
 
 /// Various ways to advance the program state.
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+///
+/// Where present, the number is a repeat count. For example, `Step(10)` executes ten
+/// instructions. Repetition ends early if Triton VM stops, for example because it halted.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub(crate) enum Execute {
     /// Continue program execution until next breakpoint.
-    Continue,
+    Continue(u32),
 
     /// Execute a single instruction.
-    Step,
+    Step(u32),
 
     /// Execute a single instruction, stepping over `call`s.
-    Next,
+    Next(u32),
 
     /// Execute instructions until the current `call` returns.
-    Finish,
+    Finish(u32),
+
+    /// Go back in time to the previous breakpoint.
+    ReverseContinue(u32),
+
+    /// Go back in time by a single instruction.
+    ReverseStep(u32),
+
+    /// Go back in time by a single instruction, stepping over `call`s.
+    ReverseNext(u32),
+
+    /// Go back in time to the `call` that entered the current function.
+    ReverseFinish(u32),
+
+    /// Execute until the instruction pointer reaches the given location or the current call
+    /// returns, whichever happens first. Stops early at breakpoints.
+    RunTo(Location),
+
+    /// Go to the state after the given number of executed instructions, either by executing
+    /// forwards or by travelling back in time.
+    GotoCycle(u32),
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Arbitrary)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
 pub(crate) enum Toggle {
     All,
     TypeHint,
     CallStack,
     SpongeState,
     Input,
+    TableHeights,
+    Heatmap,
     BlockAddress,
 }
 
+/// A change to the public input not yet read by the program. Indices refer to the pending
+/// elements, the next one to be read first.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub(crate) enum InputEdit {
+    Append(Vec<BFieldElement>),
+    Delete(usize),
+    Modify(usize, BFieldElement),
+}
+
+/// Input the next instruction, either `read_io` or `divine`, needs but that is not available.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct InputRequest {
+    pub instruction: Instruction,
+
+    /// The number of elements missing for the instruction to execute.
+    pub num_missing: usize,
+}
+
+impl InputRequest {
+    /// The request of the given instruction if it needs more input than there is, given the
+    /// number of available public and secret input elements.
+    pub fn for_instruction(
+        instruction: Instruction,
+        num_public_input_elements: usize,
+        num_secret_input_elements: usize,
+    ) -> Option<Self> {
+        let (num_required, num_available) = match instruction {
+            Instruction::ReadIo(n) => (n.num_words(), num_public_input_elements),
+            Instruction::Divine(n) => (n.num_words(), num_secret_input_elements),
+            _ => return None,
+        };
+        let num_missing = num_required.checked_sub(num_available)?;
+        (num_missing > 0).then_some(Self {
+            instruction,
+            num_missing,
+        })
+    }
+
+    pub fn is_for_public_input(self) -> bool {
+        matches!(self.instruction, Instruction::ReadIo(_))
+    }
+}
+
+impl fmt::Display for InputRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input = if self.is_for_public_input() {
+            "public"
+        } else {
+            "secret"
+        };
+        let instruction = self.instruction;
+        let num_missing = self.num_missing;
+        write!(
+            f,
+            "“{instruction}” needs {num_missing} more {input} input element(s)"
+        )
+    }
+}
+
+/// A single read from or write to RAM, performed by an instruction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub(crate) struct RamAccess {
+    pub address: BFieldElement,
+    pub is_write: bool,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Arbitrary)]
 pub(crate) struct ExecutedInstruction {
     pub instruction: Instruction,
@@ -99,27 +284,32 @@ impl<'de> Deserialize<'de> for Action {
                     "Quit" => Ok(Action::Quit),
                     "Refresh" => Ok(Action::Refresh),
 
-                    "Continue" => Ok(Action::Execute(Execute::Continue)),
-                    "Step" => Ok(Action::Execute(Execute::Step)),
-                    "Next" => Ok(Action::Execute(Execute::Next)),
-                    "Finish" => Ok(Action::Execute(Execute::Finish)),
-
                     "Undo" => Ok(Action::Undo),
+                    "Redo" => Ok(Action::Redo),
                     "Reset" => Ok(Action::Reset),
+                    "Reload" => Ok(Action::Reload),
+                    "Prove" => Ok(Action::Prove),
 
                     "ToggleAll" => Ok(Action::Toggle(Toggle::All)),
                     "ToggleTypeHintDisplay" => Ok(Action::Toggle(Toggle::TypeHint)),
                     "ToggleCallStackDisplay" => Ok(Action::Toggle(Toggle::CallStack)),
                     "ToggleSpongeStateDisplay" => Ok(Action::Toggle(Toggle::SpongeState)),
                     "ToggleInputDisplay" => Ok(Action::Toggle(Toggle::Input)),
+                    "ToggleTableHeightDisplay" => Ok(Action::Toggle(Toggle::TableHeights)),
+                    "ToggleHeatmapDisplay" => Ok(Action::Toggle(Toggle::Heatmap)),
                     "ToggleBlockAddressDisplay" => Ok(Action::Toggle(Toggle::BlockAddress)),
 
+                    "ToggleBreakpoint" => Ok(Action::ToggleBreakpoint),
+                    "RunToCursor" => Ok(Action::RunToCursor),
+
                     "HideHelpScreen" => Ok(Action::HideHelpScreen),
+                    "DumpState" => Ok(Action::DumpState(None, true)),
+                    "ReplayNext" => Ok(Action::ReplayNext),
 
                     mode if mode.starts_with("Mode::") => Self::parse_mode(mode),
                     data if data.starts_with("Error(") => Ok(Self::parse_error(data)),
                     data if data.starts_with("Resize(") => Self::parse_resize(data),
-                    _ => Err(E::custom(format!("Unknown Action variant: {value}"))),
+                    _ => Self::parse_execute(value),
                 }
             }
         }
@@ -137,6 +327,36 @@ impl<'de> Deserialize<'de> for Action {
                 Ok(Action::Mode(mode))
             }
 
+            /// Parse, for example, `Step` or `Step(10)`.
+            fn parse_execute<E>(data: &str) -> Result<Action, E>
+            where
+                E: Error,
+            {
+                let (variant, count) = match data.split_once('(') {
+                    Some((variant, count)) => {
+                        let count = count
+                            .strip_suffix(')')
+                            .and_then(|count| count.trim().parse::<u32>().ok())
+                            .filter(|&count| count > 0)
+                            .ok_or(E::custom(format!("Invalid repeat count: {data}")))?;
+                        (variant, count)
+                    }
+                    None => (data, 1),
+                };
+                let execute = match variant {
+                    "Continue" => Execute::Continue(count),
+                    "Step" => Execute::Step(count),
+                    "Next" => Execute::Next(count),
+                    "Finish" => Execute::Finish(count),
+                    "ReverseContinue" => Execute::ReverseContinue(count),
+                    "ReverseStep" => Execute::ReverseStep(count),
+                    "ReverseNext" => Execute::ReverseNext(count),
+                    "ReverseFinish" => Execute::ReverseFinish(count),
+                    _ => return Err(E::custom(format!("Unknown Action variant: {data}"))),
+                };
+                Ok(Action::Execute(execute))
+            }
+
             fn parse_error(data: &str) -> Action {
                 let error_msg = data.trim_start_matches("Error(").trim_end_matches(')');
                 Action::Error(error_msg.to_string())
@@ -165,6 +385,39 @@ impl<'de> Deserialize<'de> for Action {
     }
 }
 
+impl Execute {
+    pub fn is_reverse(&self) -> bool {
+        matches!(
+            self,
+            Self::ReverseContinue(_)
+                | Self::ReverseStep(_)
+                | Self::ReverseNext(_)
+                | Self::ReverseFinish(_)
+        )
+    }
+
+    /// Repeat this way of executing the given number of times, _i.e._, multiply its repeat count.
+    /// Has no effect on variants without a repeat count.
+    pub fn repeated(self, times: u32) -> Self {
+        match self {
+            Self::Continue(count) => Self::Continue(count.saturating_mul(times)),
+            Self::Step(count) => Self::Step(count.saturating_mul(times)),
+            Self::Next(count) => Self::Next(count.saturating_mul(times)),
+            Self::Finish(count) => Self::Finish(count.saturating_mul(times)),
+            Self::ReverseContinue(count) => Self::ReverseContinue(count.saturating_mul(times)),
+            Self::ReverseStep(count) => Self::ReverseStep(count.saturating_mul(times)),
+            Self::ReverseNext(count) => Self::ReverseNext(count.saturating_mul(times)),
+            Self::ReverseFinish(count) => Self::ReverseFinish(count.saturating_mul(times)),
+            Self::RunTo(_) | Self::GotoCycle(_) => self,
+        }
+    }
+
+    /// Whether this can change the state of a VM that is stopped, for example because it halted.
+    pub fn affects_stopped_vm(&self) -> bool {
+        self.is_reverse() || matches!(self, Self::GotoCycle(_))
+    }
+}
+
 impl ExecutedInstruction {
     pub fn new(
         instruction: Instruction,
@@ -177,4 +430,48 @@ impl ExecutedInstruction {
             new_top_of_stack,
         }
     }
+
+    /// The RAM accesses performed by the executed instruction.
+    pub fn ram_accesses(&self) -> Vec<RamAccess> {
+        RamAccess::of(self.instruction, &self.old_top_of_stack)
+    }
+}
+
+impl RamAccess {
+    /// The RAM accesses the given instruction performs when executed with the given top of the
+    /// stack. Accesses by `xx_dot_step` and `xb_dot_step` are listed operand by operand, the
+    /// left-hand side first.
+    pub fn of(instruction: Instruction, top_of_stack: &TopOfStack) -> Vec<Self> {
+        let read = |address| Self {
+            address,
+            is_write: false,
+        };
+        let write = |address| Self {
+            address,
+            is_write: true,
+        };
+        let consecutive = |start: BFieldElement, len: u64| (0..len).map(move |i| start + bfe!(i));
+
+        let pointer = top_of_stack[0];
+        match instruction {
+            Instruction::ReadMem(n) => (0..n.num_words() as u64)
+                .map(|i| read(pointer - bfe!(i)))
+                .collect(),
+            Instruction::WriteMem(n) => consecutive(pointer, n.num_words() as u64)
+                .map(write)
+                .collect(),
+            Instruction::SpongeAbsorbMem => {
+                consecutive(pointer, tip5::RATE as u64).map(read).collect()
+            }
+            Instruction::XxDotStep => consecutive(top_of_stack[1], EXTENSION_DEGREE as u64)
+                .chain(consecutive(pointer, EXTENSION_DEGREE as u64))
+                .map(read)
+                .collect(),
+            Instruction::XbDotStep => consecutive(top_of_stack[1], EXTENSION_DEGREE as u64)
+                .chain(consecutive(pointer, 1))
+                .map(read)
+                .collect(),
+            _ => vec![],
+        }
+    }
 }
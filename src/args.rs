@@ -22,6 +22,7 @@ lazy_static! {
 }
 
 pub(crate) const DEFAULT_INTERRUPT_CYCLE: u32 = 1_000_000;
+pub(crate) const DEFAULT_HISTORY_MEMORY_LIMIT: usize = 512;
 pub(crate) const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
 pub(crate) const EXAMPLE_PROGRAM_PATH: &str = "examples/program.tasm";
 include!(concat!(env!("OUT_DIR"), "/version.rs"));
@@ -46,12 +47,99 @@ pub(crate) struct TuiArgs {
         value_parser = value_parser!(u32).range(1..)
     )]
     pub interrupt_cycle: u32,
+
+    /// The maximum amount of memory, in MiB, to spend on the execution history
+    /// backing undo and reverse execution. Exceeding it makes travelling back
+    /// slower, but never impossible
+    #[arg(
+        long,
+        value_name = "MiB",
+        default_value = DEFAULT_HISTORY_MEMORY_LIMIT.to_string(),
+    )]
+    pub history_memory_limit: usize,
+
+    /// Write the execution profile to this file when quitting. Files ending
+    /// in `.json` use the speedscope format, all others use folded stacks
+    /// as understood by flamegraph tools
+    #[arg(long, value_name = "file")]
+    pub profile: Option<String>,
+
+    /// Instead of starting the TUI, run the program and write a coverage
+    /// report to this file. Files ending in `.json` get JSON, files ending in
+    /// `.info` or `.lcov` get LCOV, all others get plain text. Runs that do not
+    /// halt before the interrupt cycle count as failed
+    #[arg(long, value_name = "file", requires = "program")]
+    pub coverage: Option<String>,
+
+    /// Additional file containing public input for the coverage report. The
+    /// program runs once per input. Can be given multiple times
+    #[arg(long, value_name = "file", requires = "coverage")]
+    pub coverage_input: Vec<String>,
+
+    /// Instead of starting the TUI, run the program until it halts, fails, or
+    /// reaches the interrupt cycle, then print public output, cycle count, and
+    /// final stack. Exits with 0 if the program halted or the script ran to its
+    /// end, 3 if an instruction failed, 4 if execution was interrupted, and 5 if
+    /// an `expect` of the script failed. Catchpoints from the config file do not
+    /// apply; set them in a script instead
+    #[arg(long, conflicts_with = "coverage")]
+    pub headless: bool,
+
+    /// File containing debugger commands, one per line, to run before the TUI
+    /// starts. In headless mode, runs the commands instead of running the
+    /// program to completion. Besides the commands of the command line, knows
+    /// `continue`, `step`, `next`, `finish`, `expect <condition>`, and
+    /// `dump <file>`
+    #[arg(long, value_name = "file", conflicts_with = "coverage")]
+    pub script: Option<String>,
+
+    /// Record user-driven actions, like executing, undoing, and switching
+    /// screens, to this session file
+    #[arg(long, value_name = "file")]
+    pub record: Option<String>,
+
+    /// Replay the actions of a session file recorded with `--record`
+    #[arg(long, value_name = "file")]
+    pub replay: Option<String>,
+
+    /// Replay one action at a time, on request, instead of all at full speed
+    #[arg(long, requires = "replay")]
+    pub replay_stepwise: bool,
+
+    /// Reload the program, public input, and non-determinism whenever one of
+    /// their files changes, then run to the same cycle or, if the program
+    /// changed, to the same function at the same call depth
+    #[arg(long, requires = "program", conflicts_with = "initial_state")]
+    pub watch: bool,
+
+    /// When `read_io` or `divine` would read past the end of their input,
+    /// pause and prompt for the missing elements instead of failing. Command
+    /// `export-input` saves the session's input, including entered elements
+    #[arg(long, conflicts_with_all = ["coverage", "headless"])]
+    pub interactive_input: bool,
+
+    /// Proof to verify, as written by command `save-proof`. Starts on the
+    /// Verifier screen. If a program is given, also checks that the claim is
+    /// about it
+    #[arg(
+        long,
+        value_name = "file",
+        requires = "claim",
+        conflicts_with_all = ["coverage", "headless"]
+    )]
+    pub proof: Option<String>,
+
+    /// The claim the proof to verify is about, as written by command
+    /// `save-proof`
+    #[arg(long, value_name = "file", requires = "proof")]
+    pub claim: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Args)]
 #[group(required = false, multiple = true, conflicts_with = "state")]
 pub(crate) struct InputArgs {
     /// File containing the program to run
+    #[arg(required = false, required_unless_present_any = ["initial_state", "proof"])]
     pub program: String,
 
     /// File containing public input
@@ -75,6 +163,19 @@ impl Default for TuiArgs {
             input_args,
             initial_state: None,
             interrupt_cycle: DEFAULT_INTERRUPT_CYCLE,
+            history_memory_limit: DEFAULT_HISTORY_MEMORY_LIMIT,
+            profile: None,
+            coverage: None,
+            coverage_input: vec![],
+            headless: false,
+            script: None,
+            record: None,
+            replay: None,
+            replay_stepwise: false,
+            watch: false,
+            interactive_input: false,
+            proof: None,
+            claim: None,
         }
     }
 }
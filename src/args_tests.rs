@@ -1,6 +1,7 @@
 //! Tests for the command line arguments of the TUI.
 //! Lives in a dedicated file because `src/args.rs` is `include!`d in `build.rs`.
 
+use assert2::assert;
 use assert2::let_assert;
 use clap::Parser;
 
@@ -102,6 +103,22 @@ fn argument_program_and_public_input_and_secret_input_is_valid() {
     TuiArgs::parse_from(args);
 }
 
+#[test]
+fn argument_history_memory_limit_is_valid() {
+    let limit = vec!["--history-memory-limit".into(), "64".into()];
+    let args = [binary_name(), tui_arg_program(), limit].concat();
+    let_assert!(Ok(args) = TuiArgs::try_parse_from(args));
+    assert!(64 == args.history_memory_limit);
+}
+
+#[test]
+fn argument_profile_is_valid() {
+    let profile = vec!["--profile".into(), "profile.json".into()];
+    let args = [binary_name(), tui_arg_program(), profile].concat();
+    let_assert!(Ok(args) = TuiArgs::try_parse_from(args));
+    assert!(Some("profile.json") == args.profile.as_deref());
+}
+
 #[test]
 fn argument_initial_state_conflicts_with_program() {
     let args = [binary_name(), tui_arg_program(), tui_arg_initial_state()].concat();
@@ -131,3 +148,81 @@ fn argument_initial_state_conflicts_with_non_determinism() {
     .concat();
     let_assert!(Err(_) = TuiArgs::try_parse_from(args));
 }
+
+#[test]
+fn argument_coverage_inputs_require_coverage() {
+    let coverage_input = vec!["--coverage-input".into(), "more_input.txt".into()];
+    let args = [binary_name(), tui_arg_program(), coverage_input.clone()].concat();
+    let_assert!(Err(_) = TuiArgs::try_parse_from(args));
+
+    let coverage = vec!["--coverage".into(), "coverage.info".into()];
+    let args = [binary_name(), tui_arg_program(), coverage, coverage_input].concat();
+    let_assert!(Ok(args) = TuiArgs::try_parse_from(args));
+    assert!(Some("coverage.info") == args.coverage.as_deref());
+    assert!(vec!["more_input.txt".to_string()] == args.coverage_input);
+}
+
+#[test]
+fn argument_headless_conflicts_with_coverage() {
+    let headless = vec!["--headless".into()];
+    let args = [binary_name(), tui_arg_program(), headless.clone()].concat();
+    let_assert!(Ok(args) = TuiArgs::try_parse_from(args));
+    assert!(args.headless);
+
+    let coverage = vec!["--coverage".into(), "coverage.txt".into()];
+    let args = [binary_name(), tui_arg_program(), headless, coverage].concat();
+    let_assert!(Err(_) = TuiArgs::try_parse_from(args));
+}
+
+#[test]
+fn argument_script_is_valid() {
+    let script = vec!["--script".into(), "repro.txt".into(), "--headless".into()];
+    let args = [binary_name(), tui_arg_program(), script].concat();
+    let_assert!(Ok(args) = TuiArgs::try_parse_from(args));
+    assert!(Some("repro.txt") == args.script.as_deref());
+}
+
+#[test]
+fn argument_watch_requires_program() {
+    let watch = vec!["--watch".into()];
+    let args = [binary_name(), tui_arg_program(), watch.clone()].concat();
+    let_assert!(Ok(args) = TuiArgs::try_parse_from(args));
+    assert!(args.watch);
+
+    let args = [binary_name(), tui_arg_initial_state(), watch].concat();
+    let_assert!(Err(_) = TuiArgs::try_parse_from(args));
+}
+
+#[test]
+fn argument_interactive_input_is_valid_only_with_tui() {
+    let interactive_input = vec!["--interactive-input".into()];
+    let args = [binary_name(), tui_arg_program(), interactive_input.clone()].concat();
+    let_assert!(Ok(args) = TuiArgs::try_parse_from(args));
+    assert!(args.interactive_input);
+
+    let headless = vec!["--headless".into()];
+    let args = [
+        binary_name(),
+        tui_arg_program(),
+        interactive_input,
+        headless,
+    ]
+    .concat();
+    let_assert!(Err(_) = TuiArgs::try_parse_from(args));
+}
+
+#[test]
+fn arguments_proof_and_claim_require_each_other() {
+    let proof = vec!["--proof".into(), "proof.json".into()];
+    let claim = vec!["--claim".into(), "claim.json".into()];
+    let args = [binary_name(), proof.clone()].concat();
+    let_assert!(Err(_) = TuiArgs::try_parse_from(args));
+    let args = [binary_name(), claim.clone()].concat();
+    let_assert!(Err(_) = TuiArgs::try_parse_from(args));
+
+    let args = [binary_name(), proof, claim].concat();
+    let_assert!(Ok(args) = TuiArgs::try_parse_from(args));
+    assert!(Some("proof.json") == args.proof.as_deref());
+    assert!(Some("claim.json") == args.claim.as_deref());
+    assert!(None == args.input_args);
+}
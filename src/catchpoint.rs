@@ -0,0 +1,207 @@
+use std::fmt;
+use std::fmt::Display;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde::de::Error;
+use triton_vm::isa::instruction::ALL_INSTRUCTION_NAMES;
+use triton_vm::isa::instruction::Instruction;
+use triton_vm::prelude::BFieldElement;
+use triton_vm::prelude::Program;
+
+/// Stops execution on certain kinds of events instead of at certain addresses.
+///
+/// Written like `hash`, `divine 1`, `call recursive_function`, or `io`, both in the config and on
+/// the command line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum Catchpoint {
+    /// Stop right before executing an instruction with the given name and, if present, argument.
+    Instruction {
+        name: String,
+        argument: Option<BFieldElement>,
+    },
+
+    /// Stop right after a `call` entered the given label.
+    Call(String),
+
+    /// Stop right before executing an instruction that reads input or writes output, _i.e._, any
+    /// of `read_io`, `write_io`, `divine`, `merkle_step`, and `merkle_step_mem`.
+    Io,
+}
+
+impl Catchpoint {
+    pub fn parse(catchpoint: &str) -> Result<Self> {
+        let catchpoint = catchpoint.trim();
+        let (name, argument) = catchpoint
+            .split_once(char::is_whitespace)
+            .unwrap_or((catchpoint, ""));
+        let argument = argument.trim();
+
+        if name == "io" && argument.is_empty() {
+            return Ok(Self::Io);
+        }
+        if !ALL_INSTRUCTION_NAMES.contains(&name) {
+            bail!("unknown instruction “{name}”");
+        }
+        if argument.is_empty() {
+            let name = name.to_string();
+            return Ok(Self::Instruction {
+                name,
+                argument: None,
+            });
+        }
+        if argument.contains(char::is_whitespace) {
+            bail!("unexpected arguments after “{name}”");
+        }
+
+        match argument.parse::<BFieldElement>() {
+            Ok(argument) => Ok(Self::Instruction {
+                name: name.to_string(),
+                argument: Some(argument),
+            }),
+            Err(_) if name == "call" => Ok(Self::Call(argument.to_string())),
+            Err(err) => Err(anyhow!("invalid argument “{argument}”: {err}")),
+        }
+    }
+
+    /// Whether this catchpoint triggers before the given instruction is executed.
+    pub fn is_triggered_before(&self, instruction: Instruction) -> bool {
+        match self {
+            Self::Instruction { name, argument } => {
+                let argument_matches = argument.is_none_or(|arg| instruction.arg() == Some(arg));
+                instruction.name() == name && argument_matches
+            }
+            Self::Call(_) => false,
+            Self::Io => matches!(
+                instruction,
+                Instruction::ReadIo(_)
+                    | Instruction::WriteIo(_)
+                    | Instruction::Divine(_)
+                    | Instruction::MerkleStep
+                    | Instruction::MerkleStepMem
+            ),
+        }
+    }
+
+    /// Whether this catchpoint triggers after the given instruction was executed.
+    pub fn is_triggered_after(&self, instruction: Instruction, program: &Program) -> bool {
+        let Self::Call(label) = self else {
+            return false;
+        };
+        let Instruction::Call(address) = instruction else {
+            return false;
+        };
+        program.label_for_address(address.value()) == *label
+    }
+}
+
+impl Display for Catchpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Instruction {
+                name,
+                argument: None,
+            } => write!(f, "{name}"),
+            Self::Instruction {
+                name,
+                argument: Some(argument),
+            } => write!(f, "{name} {argument}"),
+            Self::Call(label) => write!(f, "call {label}"),
+            Self::Io => write!(f, "io"),
+        }
+    }
+}
+
+impl Serialize for Catchpoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Catchpoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let catchpoint = String::deserialize(deserializer)?;
+        Self::parse(&catchpoint).map_err(|err| D::Error::custom(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+    use triton_vm::isa::op_stack::NumberOfWords;
+    use triton_vm::prelude::bfe;
+    use triton_vm::prelude::triton_program;
+
+    use super::*;
+
+    #[test]
+    fn parse_catchpoints() {
+        let_assert!(Ok(Catchpoint::Io) = Catchpoint::parse("io"));
+
+        let_assert!(Ok(Catchpoint::Instruction { name, argument }) = Catchpoint::parse("hash"));
+        assert!("hash" == name);
+        assert!(None == argument);
+
+        let_assert!(Ok(catchpoint) = Catchpoint::parse(" divine  3 "));
+        let_assert!(Catchpoint::Instruction { name, argument } = &catchpoint);
+        assert!("divine" == name);
+        assert!(Some(bfe!(3)) == *argument);
+        assert!("divine 3" == catchpoint.to_string());
+
+        let_assert!(Ok(Catchpoint::Call(label)) = Catchpoint::parse("call my_loop"));
+        assert!("my_loop" == label);
+
+        assert!(let Err(_) = Catchpoint::parse(""));
+        assert!(let Err(_) = Catchpoint::parse("frobnicate"));
+        assert!(let Err(_) = Catchpoint::parse("push foo"));
+        assert!(let Err(_) = Catchpoint::parse("push 1 2"));
+    }
+
+    #[test]
+    fn instruction_catchpoints_respect_argument() {
+        let any_divine = Catchpoint::parse("divine").unwrap();
+        let divine_3 = Catchpoint::parse("divine 3").unwrap();
+        let divine = Instruction::Divine;
+
+        assert!(any_divine.is_triggered_before(divine(NumberOfWords::N1)));
+        assert!(any_divine.is_triggered_before(divine(NumberOfWords::N3)));
+        assert!(!divine_3.is_triggered_before(divine(NumberOfWords::N1)));
+        assert!(divine_3.is_triggered_before(divine(NumberOfWords::N3)));
+        assert!(!divine_3.is_triggered_before(Instruction::Hash));
+    }
+
+    #[test]
+    fn io_catchpoint_triggers_on_input_and_output() {
+        let io = Catchpoint::Io;
+        assert!(io.is_triggered_before(Instruction::ReadIo(NumberOfWords::N2)));
+        assert!(io.is_triggered_before(Instruction::WriteIo(NumberOfWords::N1)));
+        assert!(io.is_triggered_before(Instruction::Divine(NumberOfWords::N5)));
+        assert!(io.is_triggered_before(Instruction::MerkleStep));
+        assert!(!io.is_triggered_before(Instruction::Hash));
+    }
+
+    #[test]
+    fn call_catchpoint_triggers_after_entering_label() {
+        let program = triton_program!(call foo call bar halt foo: return bar: return);
+        let foo = Catchpoint::parse("call foo").unwrap();
+
+        let call_foo = Instruction::Call(bfe!(5));
+        let call_bar = Instruction::Call(bfe!(6));
+        assert!(foo.is_triggered_after(call_foo, &program));
+        assert!(!foo.is_triggered_after(call_bar, &program));
+        assert!(!foo.is_triggered_before(call_foo));
+    }
+
+    #[test]
+    fn deserialize_catchpoints_from_json() {
+        let_assert!(Ok(catchpoints) = serde_json::from_str::<Vec<Catchpoint>>(r#"["hash", "io"]"#));
+        assert!([Catchpoint::parse("hash").unwrap(), Catchpoint::Io] == catchpoints[..]);
+        assert!(let Err(_) = serde_json::from_str::<Vec<Catchpoint>>(r#"["frobnicate"]"#));
+    }
+}
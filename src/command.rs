@@ -0,0 +1,367 @@
+//! Commands typed into the command line of the [`Home`][home] screen.
+//!
+//! [home]: crate::components::home::Home
+
+use std::fmt;
+use std::fmt::Display;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::bail;
+use serde::Deserialize;
+use serde::Serialize;
+use triton_vm::isa::instruction::LabelledInstruction;
+use triton_vm::prelude::Program;
+
+use crate::action::Action;
+use crate::action::Execute;
+use crate::catchpoint::Catchpoint;
+use crate::condition::Condition;
+use crate::watchpoint::AddressRange;
+use crate::watchpoint::WatchMode;
+use crate::watchpoint::Watchpoint;
+
+/// A location in the program, given either by an instruction address or by a label.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) enum Location {
+    Address(usize),
+    Label(String),
+}
+
+impl Location {
+    pub fn parse(location: &str) -> Self {
+        match location.parse() {
+            Ok(address) => Self::Address(address),
+            Err(_) => Self::Label(location.to_string()),
+        }
+    }
+
+    /// The address of the instruction this location refers to.
+    pub fn resolve(&self, program: &Program) -> Result<usize> {
+        let mut address = 0;
+        for instruction in program.labelled_instructions() {
+            match (self, instruction) {
+                (Self::Label(label), LabelledInstruction::Label(l)) if *label == l => {
+                    return Ok(address);
+                }
+                (&Self::Address(a), LabelledInstruction::Instruction(_)) if a == address => {
+                    return Ok(address);
+                }
+                (_, LabelledInstruction::Instruction(instruction)) => address += instruction.size(),
+                _ => (),
+            }
+        }
+        match self {
+            Self::Address(address) => bail!("no instruction starts at address {address}"),
+            Self::Label(label) => bail!("no label “{label}” in the program"),
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "{address}"),
+            Self::Label(label) => write!(f, "{label}"),
+        }
+    }
+}
+
+/// Parse a command into the [`Action`] it triggers. Available commands are
+/// - `break <address|label> [if <condition>]` to set a (conditional) breakpoint,
+/// - `clear <address|label>` to remove a breakpoint,
+/// - `watch <addresses>`, `rwatch <addresses>`, and `awatch <addresses>` to stop on writes to,
+///   reads from, or any access of RAM,
+/// - `unwatch <addresses>` to remove all watchpoints overlapping the given addresses,
+/// - `catch <catchpoint>` to stop on certain instructions, calls, or I/O,
+/// - `uncatch <catchpoint>` to remove a catchpoint,
+/// - `until <address|label>` to execute until the given location is reached or the current call
+///   returns,
+/// - `goto cycle <cycle>` to go to the state after the given number of executed instructions,
+/// - `checkpoint <name>` to save the current state under the given name, and `restore <name>`
+///   to go back (or forth) to it,
+/// - `profile <file>` to write the execution profile to a file; see [`Profile::export`],
+/// - `prove` to prove the run of the halted program, and `save-proof [<directory>]` to write
+///   the proof and its claim to the given directory or, without one, to the data directory,
+/// - `save-input <file>` to write the public input not yet read by the program to a file,
+/// - `export-input [<directory>]` to write the session's entire input, including input entered
+///   when asked for it, to the given directory or, without one, to the data directory,
+/// - `dump [--hints] [<file>]` to write the VM state, optionally including type hints, to a
+///   file usable as initial state. Without a file name, writes to the data directory.
+///
+/// See [`Condition`] for the syntax of conditions, [`AddressRange`] for the syntax of
+/// addresses, and [`Catchpoint`] for the syntax of catchpoints.
+///
+/// [`Profile::export`]: crate::profile::Profile::export
+pub(crate) fn parse(command: &str) -> Result<Action> {
+    let command = command.trim();
+    let (name, arguments) = command
+        .split_once(char::is_whitespace)
+        .unwrap_or((command, ""));
+    let arguments = arguments.trim();
+
+    match name {
+        "break" | "b" => parse_break(arguments),
+        "clear" => {
+            let location = parse_location(arguments)?;
+            Ok(Action::DeleteBreakpoint(location))
+        }
+        "watch" => parse_watch(arguments, WatchMode::Write),
+        "rwatch" => parse_watch(arguments, WatchMode::Read),
+        "awatch" => parse_watch(arguments, WatchMode::Access),
+        "unwatch" => {
+            let addresses = parse_address_range(arguments)?;
+            Ok(Action::DeleteWatchpoints(addresses))
+        }
+        "catch" => Ok(Action::SetCatchpoint(Catchpoint::parse(arguments)?)),
+        "uncatch" => Ok(Action::DeleteCatchpoint(Catchpoint::parse(arguments)?)),
+        "until" => {
+            let location = parse_location(arguments)?;
+            Ok(Action::Execute(Execute::RunTo(location)))
+        }
+        "goto" => parse_goto(arguments),
+        "profile" => {
+            if arguments.is_empty() {
+                bail!("missing file name");
+            }
+            Ok(Action::ExportProfile(arguments.to_string()))
+        }
+        "dump" => parse_dump(arguments),
+        "prove" if !arguments.is_empty() => bail!("unexpected arguments after “prove”"),
+        "prove" => Ok(Action::Prove),
+        "save-input" => {
+            if arguments.is_empty() {
+                bail!("missing file name");
+            }
+            Ok(Action::SavePublicInput(arguments.to_string()))
+        }
+        "export-input" => {
+            let directory = (!arguments.is_empty()).then(|| arguments.to_string());
+            Ok(Action::ExportInput(directory))
+        }
+        "save-proof" => {
+            let directory = (!arguments.is_empty()).then(|| arguments.to_string());
+            Ok(Action::SaveProof(directory))
+        }
+        "checkpoint" => Ok(Action::SaveCheckpoint(parse_checkpoint_name(arguments)?)),
+        "restore" => Ok(Action::RestoreCheckpoint(parse_checkpoint_name(arguments)?)),
+        "" => bail!("empty command"),
+        _ => bail!("unknown command “{name}”"),
+    }
+}
+
+fn parse_break(arguments: &str) -> Result<Action> {
+    let (location, condition) = arguments
+        .split_once(char::is_whitespace)
+        .unwrap_or((arguments, ""));
+    let location = parse_location(location)?;
+
+    let condition = condition.trim();
+    if condition.is_empty() {
+        return Ok(Action::SetBreakpoint(location, None));
+    }
+    let Some(condition) = condition.strip_prefix("if ") else {
+        bail!("expected “if <condition>” after breakpoint location");
+    };
+    let condition = Condition::parse(condition)?;
+    Ok(Action::SetBreakpoint(location, Some(condition)))
+}
+
+fn parse_goto(arguments: &str) -> Result<Action> {
+    let Some(("cycle", cycle)) = arguments.split_once(char::is_whitespace) else {
+        bail!("expected “cycle <cycle>” after “goto”");
+    };
+    let cycle = cycle.trim();
+    let Ok(cycle) = cycle.parse() else {
+        bail!("invalid cycle “{cycle}”");
+    };
+    Ok(Action::Execute(Execute::GotoCycle(cycle)))
+}
+
+fn parse_checkpoint_name(name: &str) -> Result<String> {
+    if name.is_empty() {
+        bail!("missing checkpoint name");
+    }
+    Ok(name.to_string())
+}
+
+fn parse_dump(arguments: &str) -> Result<Action> {
+    let (with_type_hints, path) = match arguments.strip_prefix("--hints") {
+        Some(path) if path.is_empty() || path.starts_with(char::is_whitespace) => (true, path),
+        _ => (false, arguments),
+    };
+    let path = path.trim();
+    let path = (!path.is_empty()).then(|| path.to_string());
+    Ok(Action::DumpState(path, with_type_hints))
+}
+
+fn parse_watch(arguments: &str, mode: WatchMode) -> Result<Action> {
+    let addresses = parse_address_range(arguments)?;
+    Ok(Action::SetWatchpoint(Watchpoint { addresses, mode }))
+}
+
+fn parse_address_range(addresses: &str) -> Result<AddressRange> {
+    if addresses.is_empty() {
+        bail!("missing address or address range");
+    }
+    AddressRange::parse(addresses)
+}
+
+fn parse_location(location: &str) -> Result<Location> {
+    if location.is_empty() {
+        bail!("missing address or label");
+    }
+    if location.contains(char::is_whitespace) {
+        bail!("unexpected arguments after “{location}”");
+    }
+    Ok(Location::parse(location))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+    use triton_vm::prelude::triton_program;
+
+    use super::*;
+
+    #[test]
+    fn parse_unconditional_breakpoint() {
+        let_assert!(Ok(action) = parse("break my_loop"));
+        let location = Location::Label("my_loop".to_string());
+        assert!(Action::SetBreakpoint(location, None) == action);
+
+        let_assert!(Ok(action) = parse("  b 42 "));
+        assert!(Action::SetBreakpoint(Location::Address(42), None) == action);
+    }
+
+    #[test]
+    fn parse_conditional_breakpoint() {
+        let_assert!(Ok(Action::SetBreakpoint(location, condition)) = parse("break 7 if st0 > 9"));
+        assert!(Location::Address(7) == location);
+        let_assert!(Some(condition) = condition);
+        assert!("st0 > 9" == condition.to_string());
+    }
+
+    #[test]
+    fn parse_clearing_breakpoint() {
+        let_assert!(Ok(action) = parse("clear my_loop"));
+        let location = Location::Label("my_loop".to_string());
+        assert!(Action::DeleteBreakpoint(location) == action);
+    }
+
+    #[test]
+    fn parse_watchpoints() {
+        let_assert!(Ok(Action::SetWatchpoint(watchpoint)) = parse("watch 42"));
+        assert!(WatchMode::Write == watchpoint.mode);
+        assert!("42" == watchpoint.addresses.to_string());
+
+        let_assert!(Ok(Action::SetWatchpoint(watchpoint)) = parse("rwatch 40..50"));
+        assert!(WatchMode::Read == watchpoint.mode);
+        assert!("40..=49" == watchpoint.addresses.to_string());
+
+        let_assert!(Ok(Action::SetWatchpoint(watchpoint)) = parse("awatch 40..=50"));
+        assert!(WatchMode::Access == watchpoint.mode);
+
+        let_assert!(Ok(Action::DeleteWatchpoints(addresses)) = parse("unwatch 0..100"));
+        assert!("0..=99" == addresses.to_string());
+    }
+
+    #[test]
+    fn parse_catchpoints() {
+        let_assert!(Ok(Action::SetCatchpoint(catchpoint)) = parse("catch call my_loop"));
+        assert!(Catchpoint::Call("my_loop".to_string()) == catchpoint);
+
+        let_assert!(Ok(Action::DeleteCatchpoint(catchpoint)) = parse("uncatch io"));
+        assert!(Catchpoint::Io == catchpoint);
+    }
+
+    #[test]
+    fn parse_running_until_location() {
+        let_assert!(Ok(action) = parse("until my_loop"));
+        let location = Location::Label("my_loop".to_string());
+        assert!(Action::Execute(Execute::RunTo(location)) == action);
+    }
+
+    #[test]
+    fn parse_goto_cycle() {
+        let_assert!(Ok(action) = parse("goto cycle 1234"));
+        assert!(Action::Execute(Execute::GotoCycle(1234)) == action);
+    }
+
+    #[test]
+    fn parse_profile_export() {
+        let_assert!(Ok(action) = parse("profile out/my profile.json"));
+        assert!(Action::ExportProfile("out/my profile.json".to_string()) == action);
+    }
+
+    #[test]
+    fn parse_state_dump() {
+        let_assert!(Ok(action) = parse("dump"));
+        assert!(Action::DumpState(None, false) == action);
+
+        let_assert!(Ok(action) = parse("dump --hints"));
+        assert!(Action::DumpState(None, true) == action);
+
+        let_assert!(Ok(action) = parse("dump --hints my state.json"));
+        assert!(Action::DumpState(Some("my state.json".to_string()), true) == action);
+
+        let_assert!(Ok(action) = parse("dump --hints.json"));
+        assert!(Action::DumpState(Some("--hints.json".to_string()), false) == action);
+    }
+
+    #[test]
+    fn parse_checkpoint_commands() {
+        let_assert!(Ok(action) = parse("checkpoint after setup"));
+        assert!(Action::SaveCheckpoint("after setup".to_string()) == action);
+
+        let_assert!(Ok(action) = parse("restore  before verify "));
+        assert!(Action::RestoreCheckpoint("before verify".to_string()) == action);
+    }
+
+    #[test]
+    fn parse_proof_commands() {
+        assert!(let Ok(Action::Prove) = parse("prove"));
+        assert!(let Ok(Action::SaveProof(None)) = parse("save-proof"));
+        let_assert!(Ok(action) = parse("save-proof my proofs"));
+        assert!(Action::SaveProof(Some("my proofs".to_string())) == action);
+    }
+
+    #[test]
+    fn parse_export_input_command() {
+        assert!(let Ok(Action::ExportInput(None)) = parse("export-input"));
+        let_assert!(Ok(action) = parse("export-input session"));
+        assert!(Action::ExportInput(Some("session".to_string())) == action);
+    }
+
+    #[test]
+    fn malformed_commands_are_rejected() {
+        assert!(let Err(_) = parse(""));
+        assert!(let Err(_) = parse("frobnicate"));
+        assert!(let Err(_) = parse("break"));
+        assert!(let Err(_) = parse("checkpoint"));
+        assert!(let Err(_) = parse("restore"));
+        assert!(let Err(_) = parse("prove now"));
+        assert!(let Err(_) = parse("save-input"));
+        assert!(let Err(_) = parse("break 7 st0 > 9"));
+        assert!(let Err(_) = parse("break 7 if st0"));
+        assert!(let Err(_) = parse("clear 7 8"));
+        assert!(let Err(_) = parse("watch"));
+        assert!(let Err(_) = parse("rwatch my_loop"));
+        assert!(let Err(_) = parse("catch"));
+        assert!(let Err(_) = parse("until"));
+        assert!(let Err(_) = parse("goto 42"));
+        assert!(let Err(_) = parse("goto cycle -1"));
+        assert!(let Err(_) = parse("goto cycle"));
+        assert!(let Err(_) = parse("profile"));
+    }
+
+    #[test]
+    fn resolve_locations() {
+        let program = triton_program!(push 1 call foo halt foo: pop 1 return);
+        let foo = Location::Label("foo".to_string());
+        assert!(let Ok(5) = foo.resolve(&program));
+        assert!(let Ok(2) = Location::Address(2).resolve(&program));
+        assert!(let Err(_) = Location::Address(1).resolve(&program));
+        assert!(let Err(_) = Location::Label("bar".to_string()).resolve(&program));
+    }
+}
@@ -10,9 +10,13 @@ use crate::action::Action;
 use crate::triton_vm_state::TritonVMState;
 use crate::tui::Event;
 
+pub(crate) mod checkpoints;
 pub(crate) mod help;
 pub(crate) mod home;
+pub(crate) mod input_editor;
 pub(crate) mod memory;
+pub(crate) mod profiler;
+pub(crate) mod verifier;
 
 /// `Component` is a trait that represents a visual and interactive element of the user interface.
 /// Implementors of this trait can be registered with the main application loop and will be able to
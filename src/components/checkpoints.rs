@@ -0,0 +1,206 @@
+use color_eyre::eyre::Result;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::prelude::*;
+use ratatui::widgets::Block;
+use ratatui::widgets::BorderType;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Padding;
+use ratatui::widgets::Paragraph;
+
+use crate::action::Action;
+use crate::components::Component;
+use crate::mode::Mode;
+use crate::triton_vm_state::TritonVMState;
+
+/// A small panel listing the named checkpoints. Picking one goes to it.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct Checkpoints {
+    /// The selected checkpoint.
+    cursor: usize,
+
+    /// The names of the checkpoints as most recently rendered, top to bottom.
+    rendered_names: Vec<String>,
+}
+
+impl Checkpoints {
+    const PANEL_WIDTH: u16 = 60;
+    const PANEL_HEIGHT: u16 = 16;
+
+    fn selected_name(&self) -> Option<String> {
+        self.rendered_names.get(self.cursor).cloned()
+    }
+
+    fn panel_area(area: Rect) -> Rect {
+        let [_, area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(Self::PANEL_WIDTH),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(Self::PANEL_HEIGHT),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        area
+    }
+
+    fn render_lines(&self, state: &TritonVMState) -> Vec<Line<'static>> {
+        if state.named_checkpoints.is_empty() {
+            let hint = "No checkpoints yet. Save one with “checkpoint <name>”.";
+            return vec![Line::from(hint).dim()];
+        }
+
+        let name_width = state
+            .named_checkpoints
+            .iter()
+            .map(|c| c.name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let header = format!("{:<name_width$}  {:>9}  {:>5}", "name", "cycle", "ip");
+        let mut lines = vec![Line::from(header).bold()];
+        for (index, named_checkpoint) in state.named_checkpoints.iter().enumerate() {
+            let vm_state = &named_checkpoint.checkpoint.vm_state;
+            let line = format!(
+                "{:<name_width$}  {:>9}  {:>5}",
+                named_checkpoint.name, vm_state.cycle_count, vm_state.instruction_pointer,
+            );
+            let line = Line::from(line);
+            let line = if index == self.cursor {
+                line.reversed()
+            } else {
+                line
+            };
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+impl Component for Checkpoints {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<Action>> {
+        if key_event.kind == KeyEventKind::Release || key_event.modifiers != KeyModifiers::NONE {
+            return Ok(None);
+        }
+        let action = match key_event.code {
+            KeyCode::Up => {
+                self.cursor = self.cursor.saturating_sub(1);
+                None
+            }
+            KeyCode::Down => {
+                self.cursor = self.cursor.saturating_add(1);
+                None
+            }
+            KeyCode::Enter => self.selected_name().map(Action::RestoreCheckpoint),
+            KeyCode::Char('x') | KeyCode::Delete => {
+                self.selected_name().map(Action::DeleteCheckpoint)
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Reset => *self = Self::default(),
+            Action::RestoreCheckpoint(_) => return Ok(Some(Action::Mode(Mode::Home))),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, state: &TritonVMState) -> Result<()> {
+        let num_checkpoints = state.named_checkpoints.len();
+        self.cursor = self.cursor.min(num_checkpoints.saturating_sub(1));
+        self.rendered_names = state
+            .named_checkpoints
+            .iter()
+            .map(|c| c.name.clone())
+            .collect();
+
+        let area = Self::panel_area(frame.area());
+        let block = Block::default()
+            .padding(Padding::new(1, 1, 1, 0))
+            .title(" Checkpoints ".bold())
+            .title_bottom(" ⏎ go to  x delete  esc back ".dim())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded);
+        let paragraph = Paragraph::new(self.render_lines(state)).block(block);
+        frame.render_widget(Clear, area);
+        frame.render_widget(paragraph, area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+    use ratatui::backend::TestBackend;
+    use triton_vm::prelude::*;
+
+    use crate::action::Execute;
+    use crate::args::TuiArgs;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn picking_a_checkpoint_goes_to_it() {
+        let mut state = TritonVMState::new(&TuiArgs::default()).unwrap();
+        state
+            .update(Action::SaveCheckpoint("start".to_string()))
+            .unwrap();
+        state.update(Action::Execute(Execute::Step(2))).unwrap();
+        state
+            .update(Action::SaveCheckpoint("later".to_string()))
+            .unwrap();
+        state.update(Action::Execute(Execute::Step(1))).unwrap();
+
+        let mut checkpoints = Checkpoints::default();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal
+            .draw(|frame| checkpoints.draw(frame, &state).unwrap())
+            .unwrap();
+        let_assert!(Ok(None) = checkpoints.handle_key_event(key(KeyCode::Down)));
+        let_assert!(Ok(Some(action)) = checkpoints.handle_key_event(key(KeyCode::Enter)));
+        assert!(Action::RestoreCheckpoint("later".to_string()) == action);
+
+        state.update(action.clone()).unwrap();
+        assert!(2 == state.vm_state.cycle_count);
+        let_assert!(Ok(Some(Action::Mode(Mode::Home))) = checkpoints.update(action));
+    }
+
+    #[test]
+    fn saving_under_existing_name_replaces_checkpoint() {
+        let program = triton_program!(push 1 push 2 push 3 halt);
+        let vm_state = VMState::new(program, [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        state
+            .update(Action::SaveCheckpoint("mark".to_string()))
+            .unwrap();
+        state.update(Action::Execute(Execute::Step(2))).unwrap();
+        state
+            .update(Action::SaveCheckpoint("mark".to_string()))
+            .unwrap();
+        let_assert!([named_checkpoint] = state.named_checkpoints.as_slice());
+        assert!(2 == named_checkpoint.checkpoint.vm_state.cycle_count);
+
+        state
+            .update(Action::DeleteCheckpoint("mark".to_string()))
+            .unwrap();
+        assert!(state.named_checkpoints.is_empty());
+        state
+            .update(Action::RestoreCheckpoint("mark".to_string()))
+            .unwrap();
+        assert!(state.warning.is_some());
+    }
+}
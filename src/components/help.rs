@@ -38,13 +38,27 @@ impl Component for Help {
             Help::help_line("s", "step     – execute one instruction"),
             Help::help_line("n", "next     – like “step” but steps over “call”"),
             Help::help_line("f", "finish   – step out of current “call”"),
+            Help::help_line("C/S/N/F", "like c/s/n/f, but backwards in time"),
+            Help::help_line("25s", "prefix any of the above with a count to repeat"),
             Help::help_line("u", "undo last command that advanced execution"),
+            Help::help_line("Ctrl+r", "redo last undone command"),
             Help::help_line("r", "reload files and restart Triton VM"),
+            Help::help_line("Ctrl+n", "replay next action of stepwise replayed session"),
+            String::new(),
+            Help::help_line("↑/↓", "move program cursor"),
+            Help::help_line("Esc", "reset program cursor to instruction pointer"),
+            Help::help_line("b", "toggle breakpoint at program cursor"),
+            Help::help_line("a", "advance  – execute up to program cursor"),
+            Help::help_line("d", "dump VM state and type hints to data directory"),
+            Help::help_line("P", "prove halted program, then verify the proof"),
+            Help::help_line("Enter", "focus command line"),
             String::new(),
             Help::help_line("t,a", "toggle all widgets"),
             Help::help_line("t,t", "toggle type annotations"),
             Help::help_line("t,c", "toggle call stack"),
             Help::help_line("t,i", "toggle displaying input (if any)"),
+            Help::help_line("t,h", "toggle table heights"),
+            Help::help_line("t,e", "toggle execution count heatmap"),
             String::new(),
             Help::mode_line("Memory"),
             Help::help_line("Enter", "focus text area"),
@@ -53,9 +67,78 @@ impl Component for Help {
             String::new(),
             Help::help_line("t,b", "toggle block address display"),
             String::new(),
+            Help::mode_line("Profiler"),
+            Help::help_line("↑/↓", "select function or call"),
+            Help::help_line("Tab", "switch between function table and call tree"),
+            Help::help_line("o", "change sort order of function table"),
+            Help::help_line("←/→", "collapse or expand call"),
+            Help::help_line("c/r", "continue or reset execution"),
+            String::new(),
+            Help::mode_line("Checkpoints"),
+            Help::help_line("↑/↓", "select checkpoint"),
+            Help::help_line("Enter", "go to selected checkpoint"),
+            Help::help_line("x", "delete selected checkpoint"),
+            String::new(),
+            Help::mode_line("Input editor"),
+            Help::help_line("↑/↓", "select pending input element"),
+            Help::help_line("a", "append elements"),
+            Help::help_line("Enter/e", "modify selected element"),
+            Help::help_line("x", "delete selected element"),
+            Help::help_line("w", "save pending input to file"),
+            String::new(),
+            Help::mode_line("Commands"),
+            Help::help_line("break", "break <address|label> [if <condition>]"),
+            Help::help_line("clear", "clear <address|label>"),
+            Help::help_line(
+                "watch",
+                "watch <address>[..<end>] – stop after writes to RAM",
+            ),
+            Help::help_line("rwatch", "like “watch” but for reads"),
+            Help::help_line("awatch", "like “watch” but for reads and writes"),
+            Help::help_line("unwatch", "unwatch <address>[..<end>]"),
+            Help::help_line("catch", "catch <instruction [arg]|call <label>|io>"),
+            Help::help_line("uncatch", "uncatch <instruction [arg]|call <label>|io>"),
+            Help::help_line("until", "until <address|label>"),
+            Help::help_line("goto", "goto cycle <cycle>"),
+            Help::help_line(
+                "checkpoint",
+                "checkpoint <name> – save current state by name",
+            ),
+            Help::help_line("restore", "restore <name> – go to named checkpoint"),
+            Help::help_line(
+                "profile",
+                "profile <file> – save as speedscope .json or folded stacks",
+            ),
+            Help::help_line(
+                "dump",
+                "dump [--hints] [<file>] – save state for --initial-state",
+            ),
+            Help::help_line(
+                "save-proof",
+                "save-proof [<directory>] – save proof and claim",
+            ),
+            Help::help_line(
+                "save-input",
+                "save-input <file> – save pending public input",
+            ),
+            Help::help_line(
+                "export-input",
+                "export-input [<directory>] – save all input, incl. entered",
+            ),
+            Help::help_line("", "conditions like “st0 > 1000 && loop_counter == 3”"),
+            Help::help_line(
+                "",
+                "may use st0…st15, ram[…], jump_stack_depth, cycle_count,",
+            ),
+            Help::help_line("", "and variables from type hints"),
+            String::new(),
             Help::mode_line("General"),
             Help::help_line("Esc", "show Home screen"),
             Help::help_line("m", "toggle Memory screen"),
+            Help::help_line("p", "toggle Profiler screen"),
+            Help::help_line("k", "toggle Checkpoints screen"),
+            Help::help_line("v", "toggle Verifier screen"),
+            Help::help_line("i", "toggle public input editor"),
             Help::help_line("h", "toggle Help"),
             Help::help_line("q", "quit"),
         ];
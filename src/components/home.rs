@@ -1,5 +1,9 @@
 use arbitrary::Arbitrary;
 use color_eyre::eyre::Result;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use itertools::Itertools;
 use ratatui::prelude::*;
 use ratatui::style::Styled;
@@ -9,14 +13,24 @@ use ratatui::widgets::Borders;
 use ratatui::widgets::Padding;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::block::Position;
+use strum::IntoEnumIterator;
 use triton_vm::isa::instruction::*;
 use triton_vm::isa::op_stack::NUM_OP_STACK_REGISTERS;
 use triton_vm::prelude::Program;
+use triton_vm::prelude::TableId;
 use triton_vm::prelude::Tip5;
+use tui_textarea::TextArea;
 
 use crate::action::Action;
+use crate::action::Execute;
+use crate::action::InputRequest;
 use crate::action::Toggle;
+use crate::catchpoint::Catchpoint;
+use crate::command;
+use crate::command::Location;
 use crate::element_type_hint::ElementTypeHint;
+use crate::mode::Mode;
+use crate::prover::ProverStatus;
 use crate::triton_vm_state::TritonVMState;
 
 use super::Component;
@@ -28,6 +42,28 @@ pub(crate) struct Home {
     call_stack: bool,
     sponge: bool,
     inputs: bool,
+    table_heights: bool,
+
+    /// Whether to show how often each instruction has been executed, colored by frequency.
+    heatmap: bool,
+
+    /// The address of the instruction the program cursor is on. Follows the instruction pointer
+    /// if `None`.
+    cursor: Option<usize>,
+
+    /// The instruction pointer as of the most recent rendering. Starting point for moving the
+    /// program cursor.
+    instruction_pointer: usize,
+
+    /// Command line for commands like setting conditional breakpoints. Replaces the message box
+    /// while in focus.
+    #[arbitrary(default)]
+    command_line: TextArea<'static>,
+    command_line_in_focus: bool,
+
+    /// The input execution paused for, if the command line asks for it instead of a command.
+    #[arbitrary(default)]
+    input_request: Option<InputRequest>,
 
     /// Lazily pre-rendered program. Reduces rendering time for long programs.
     rendered_program: Option<Vec<ProgramLine>>,
@@ -40,13 +76,58 @@ impl Default for Home {
             call_stack: true,
             sponge: false,
             inputs: true,
+            table_heights: true,
+            heatmap: false,
+            cursor: None,
+            instruction_pointer: 0,
+            command_line: Self::initial_command_line(),
+            command_line_in_focus: false,
+            input_request: None,
             rendered_program: None,
         }
     }
 }
 
 impl Home {
-    fn render_program(program: &Program) -> Vec<ProgramLine> {
+    fn initial_command_line() -> TextArea<'static> {
+        let mut command_line = TextArea::default();
+        command_line.set_cursor_line_style(Style::default());
+        command_line
+    }
+
+    fn submit_command(&mut self) -> Action {
+        let command = self.command_line.lines()[0].clone();
+        self.command_line = Self::initial_command_line();
+        if self.input_request.take().is_some() {
+            return Self::parse_entered_input(&command);
+        }
+        command::parse(&command).unwrap_or_else(|report| Action::Error(report.to_string()))
+    }
+
+    fn parse_entered_input(text: &str) -> Action {
+        match TritonVMState::parse_public_input(text) {
+            Ok(elements) if elements.is_empty() => Action::Error("no input entered".to_string()),
+            Ok(elements) => Action::EnterInput(elements),
+            Err(report) => Action::Error(format!("invalid input “{text}”: {report}")),
+        }
+    }
+
+    fn handle_command_line_key_event(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => {
+                self.command_line_in_focus = false;
+                self.input_request = None;
+            }
+            KeyCode::Enter => {
+                self.command_line_in_focus = false;
+                return Some(self.submit_command());
+            }
+            _ => _ = self.command_line.input(key),
+        }
+        None
+    }
+
+    pub fn render_program(program: &Program) -> Vec<ProgramLine> {
         let mut address = 0;
         let mut rendered_program = vec![];
         let mut has_breakpoint = false;
@@ -81,6 +162,43 @@ impl Home {
         max_address.to_string().len()
     }
 
+    fn cursor_address(&self) -> usize {
+        self.cursor.unwrap_or(self.instruction_pointer)
+    }
+
+    fn move_cursor(&mut self, key: KeyEvent) {
+        let Some(ref program) = self.rendered_program else {
+            return;
+        };
+        let addresses = program
+            .iter()
+            .filter_map(|line| match line {
+                ProgramLine::Instruction { address, .. } => Some(*address),
+                ProgramLine::Label(_) | ProgramLine::AssertionContext(_) => None,
+            })
+            .collect_vec();
+        let current_index = addresses
+            .binary_search(&self.cursor_address())
+            .unwrap_or_else(|index| index);
+
+        let page_size = 20;
+        let new_index = match (key.modifiers, key.code) {
+            (KeyModifiers::NONE, KeyCode::Up) => current_index.saturating_sub(1),
+            (KeyModifiers::NONE, KeyCode::Down) => current_index.saturating_add(1),
+            (KeyModifiers::NONE, KeyCode::PageUp) => current_index.saturating_sub(page_size),
+            (KeyModifiers::NONE, KeyCode::PageDown) => current_index.saturating_add(page_size),
+            (KeyModifiers::NONE, KeyCode::Esc) => {
+                self.cursor = None;
+                return;
+            }
+            _ => return,
+        };
+        let new_index = new_index.min(addresses.len().saturating_sub(1));
+        if let Some(&address) = addresses.get(new_index) {
+            self.cursor = Some(address);
+        }
+    }
+
     fn toggle_widget(&mut self, toggle: Toggle) {
         match toggle {
             Toggle::All => self.toggle_all_widgets(),
@@ -88,6 +206,8 @@ impl Home {
             Toggle::CallStack => self.call_stack = !self.call_stack,
             Toggle::SpongeState => self.sponge = !self.sponge,
             Toggle::Input => self.inputs = !self.inputs,
+            Toggle::TableHeights => self.table_heights = !self.table_heights,
+            Toggle::Heatmap => self.heatmap = !self.heatmap,
             Toggle::BlockAddress => (),
         };
     }
@@ -97,8 +217,14 @@ impl Home {
         self.set_all_widgets_visibility_to(!any_widget_is_shown);
     }
 
-    fn all_widget_visibilities(&self) -> [bool; 4] {
-        [self.type_hints, self.call_stack, self.sponge, self.inputs]
+    fn all_widget_visibilities(&self) -> [bool; 5] {
+        [
+            self.type_hints,
+            self.call_stack,
+            self.sponge,
+            self.inputs,
+            self.table_heights,
+        ]
     }
 
     fn set_all_widgets_visibility_to(&mut self, visibility: bool) {
@@ -106,6 +232,7 @@ impl Home {
         self.call_stack = visibility;
         self.sponge = visibility;
         self.inputs = visibility;
+        self.table_heights = visibility;
     }
 
     fn distribute_area_for_widgets(&self, state: &TritonVMState, area: Rect) -> WidgetAreas {
@@ -119,15 +246,22 @@ impl Home {
         } else {
             Constraint::Length(0)
         };
+        let timeline_height = Constraint::Length(2);
         let message_box_height = Constraint::Length(2);
         let constraints = [
             Constraint::Fill(1),
             public_input_height,
             secret_input_height,
+            timeline_height,
             message_box_height,
         ];
-        let [state_area, public_input, secret_input, message_box] =
-            Layout::vertical(constraints).areas(area);
+        let [
+            state_area,
+            public_input,
+            secret_input,
+            timeline,
+            message_box,
+        ] = Layout::vertical(constraints).areas(area);
 
         let op_stack_widget_width = Constraint::Length(30);
         let remaining_width = Constraint::Fill(1);
@@ -143,10 +277,16 @@ impl Home {
         let show = Constraint::Fill(1);
         let hide = Constraint::Length(0);
         let maybe_show = |is_visible| if is_visible { show } else { hide };
-        let [type_hint, program, call_stack] = Layout::horizontal([
+        let table_heights_width = if self.table_heights {
+            Constraint::Length(26)
+        } else {
+            hide
+        };
+        let [type_hint, program, call_stack, table_heights] = Layout::horizontal([
             maybe_show(self.type_hints),
             show,
             maybe_show(self.call_stack),
+            table_heights_width,
         ])
         .areas(remaining_area);
 
@@ -155,9 +295,11 @@ impl Home {
             type_hint,
             program,
             call_stack,
+            table_heights,
             sponge,
             public_input,
             secret_input,
+            timeline,
             message_box,
         }
     }
@@ -237,6 +379,7 @@ impl Home {
         let block = Block::default()
             .padding(Padding::new(1, 1, 1, 0))
             .title(format!(" Program (cycle: {cycle_count:>5}) "))
+            .title_bottom(Self::history_summary(state).dim())
             .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
             .border_set(border_set);
 
@@ -248,18 +391,21 @@ impl Home {
             frame.render_widget(err, render_area);
             return;
         };
-        let Some(idx_of_line_with_ip) = Self::line_index_of_address(program, ip) else {
-            let err = Paragraph::new(format!("\nNo instruction at address {ip}!").red()).centered();
-            frame.render_widget(err, render_area);
+        let focus = self.cursor_address();
+        let Some(idx_of_focused_line) = Self::line_index_of_address(program, focus) else {
+            let err = Paragraph::new(format!("\nNo instruction at address {focus}!").red());
+            frame.render_widget(err.centered(), render_area);
             return;
         };
-        let idx_of_first_line = idx_of_line_with_ip
+        let idx_of_first_line = idx_of_focused_line
             .saturating_add(render_area_height / 2)
             .min(program.len())
             .saturating_sub(render_area_height);
 
         let mut text = Vec::<Line>::new();
         let address_width = Self::address_render_width(&state.vm_state.program);
+        let max_execution_count = state.profile.max_execution_count();
+        let execution_count_width = max_execution_count.to_string().len();
 
         // a bit of overdraw is better than empty lines
         let num_lines_with_overdraw = 2 * render_area_height;
@@ -280,12 +426,26 @@ impl Home {
                     } else {
                         " ".into()
                     };
-                    let gutter = if has_breakpoint {
-                        format!("{:>address_width$}  ", "🔴").into()
-                    } else {
-                        format!(" {address:>address_width$}  ").dim()
+                    let gutter = match (has_breakpoint, state.breakpoints.get(&address)) {
+                        (true, _) => format!("{:>address_width$}  ", "🔴").into(),
+                        (false, Some(None)) => format!("{:>address_width$}  ", "🟠").into(),
+                        (false, Some(Some(_))) => format!("{:>address_width$}  ", "🟡").into(),
+                        (false, None) => format!(" {address:>address_width$}  ").dim(),
                     };
-                    ip + gutter + Span::from(instruction.to_string())
+                    let mut line = ip + gutter;
+                    if self.heatmap {
+                        let count = state.profile.execution_count(address);
+                        let heat = format!("{count:>execution_count_width$}");
+                        let heat = heat.fg(Self::heat_color(count, max_execution_count));
+                        line.push_span(heat);
+                        line.push_span("  ");
+                    }
+                    line.push_span(instruction.to_string());
+                    if self.cursor == Some(address) {
+                        line.reversed()
+                    } else {
+                        line
+                    }
                 }
                 &ProgramLine::AssertionContext(AssertionContext::ID(id)) => {
                     if let Some(line) = text.last_mut() {
@@ -302,6 +462,40 @@ impl Home {
         frame.render_widget(paragraph, render_area);
     }
 
+    /// The color of an execution count in the heatmap. Counts are compared on a logarithmic
+    /// scale, making instructions in nested loops stand out from those in simple loops.
+    fn heat_color(count: u32, max_count: u32) -> Color {
+        const COLORS: [Color; 5] = [
+            Color::Blue,
+            Color::Cyan,
+            Color::Green,
+            Color::Yellow,
+            Color::Red,
+        ];
+        if count == 0 {
+            return Color::DarkGray;
+        }
+        let heat = f64::from(count).ln_1p() / f64::from(max_count).ln_1p();
+        let index = (heat * (COLORS.len() - 1) as f64).round() as usize;
+        COLORS[index.min(COLORS.len() - 1)]
+    }
+
+    /// How many actions can be undone and redone, and how much memory the execution history
+    /// takes up.
+    fn history_summary(state: &TritonVMState) -> String {
+        const MIB: f64 = (1 << 20) as f64;
+        let num_undos = state.undo_stack.len();
+        let num_redos = state.redo_stack.len();
+        let history = &state.history;
+        let num_checkpoints = history.num_checkpoints();
+        let memory_usage = history.memory_usage() as f64 / MIB;
+        let memory_limit = history.memory_limit() as f64 / MIB;
+        format!(
+            " undo: {num_undos} │ redo: {num_redos} │ history: {num_checkpoints} checkpoints, \
+            {memory_usage:.1}/{memory_limit:.0} MiB "
+        )
+    }
+
     /// Requires the [`ProgramLine`]s to be sorted by their address. Variants
     /// without an `address` field cannot be found this way.
     fn line_index_of_address(lines: &[ProgramLine], address_to_find: usize) -> Option<usize> {
@@ -360,6 +554,70 @@ impl Home {
         frame.render_widget(paragraph, render_area);
     }
 
+    fn render_table_heights_widget(&self, frame: &mut Frame<'_>, render_info: RenderInfo) {
+        if !self.table_heights {
+            return;
+        }
+
+        let border_set = symbols::border::Set {
+            top_left: symbols::line::ROUNDED.horizontal_down,
+            bottom_left: symbols::line::ROUNDED.horizontal_up,
+            ..symbols::border::ROUNDED
+        };
+        let block = Block::default()
+            .padding(Padding::new(1, 1, 1, 0))
+            .title(" Table Heights ")
+            .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
+            .border_set(border_set);
+
+        let state = render_info.state;
+        let text = Self::render_table_heights(state);
+        let paragraph = Paragraph::new(text).block(block).alignment(Alignment::Left);
+        frame.render_widget(paragraph, render_info.areas.table_heights);
+    }
+
+    fn render_table_heights(state: &TritonVMState) -> Vec<Line<'static>> {
+        let table_heights = &state.table_heights;
+        let dominant_table = table_heights.dominant_table();
+        let mut text = vec![];
+        for table in TableId::iter() {
+            let height = table_heights.height(table);
+            let line = Line::from(format!("{:<11}{height:>12}", Self::table_name(table)));
+            let line = if table == dominant_table {
+                line.bold()
+            } else {
+                line
+            };
+            text.push(line);
+        }
+
+        let padded_height = table_heights.padded_height();
+        text.push(Line::from(""));
+        text.push(Line::from(format!("{:<11}{padded_height:>12}", "padded")).bold());
+        if let Some(next_padded_height) = state.padded_height_after_next_instruction
+            && next_padded_height > padded_height
+        {
+            text.push(Line::from(""));
+            text.push(Line::from("next instruction grows").yellow());
+            text.push(Line::from(format!("padded height to {next_padded_height}")).yellow());
+        }
+        text
+    }
+
+    fn table_name(table: TableId) -> &'static str {
+        match table {
+            TableId::Program => "program",
+            TableId::Processor => "processor",
+            TableId::OpStack => "op stack",
+            TableId::Ram => "ram",
+            TableId::JumpStack => "jump stack",
+            TableId::Hash => "hash",
+            TableId::Cascade => "cascade",
+            TableId::Lookup => "lookup",
+            TableId::U32 => "u32",
+        }
+    }
+
     fn render_sponge_widget(&self, frame: &mut Frame<'_>, render_info: RenderInfo) {
         let border_set = symbols::border::Set {
             top_left: symbols::line::ROUNDED.horizontal_down,
@@ -458,6 +716,73 @@ impl Home {
         Some(header + colon + input + footer)
     }
 
+    fn render_timeline_widget(&self, frame: &mut Frame<'_>, render_info: RenderInfo) {
+        let border_set = symbols::border::Set {
+            bottom_left: symbols::line::ROUNDED.vertical_right,
+            bottom_right: symbols::line::ROUNDED.vertical_left,
+            ..symbols::border::ROUNDED
+        };
+        let block = Block::default()
+            .padding(Padding::horizontal(1))
+            .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            .border_set(border_set);
+        let render_area = render_info.areas.timeline;
+        let width = usize::from(block.inner(render_area).width);
+        let timeline = Self::render_timeline(render_info.state, width);
+        let paragraph = Paragraph::new(timeline).block(block);
+        frame.render_widget(paragraph, render_area);
+    }
+
+    /// A bar spanning all cycles seen since the last reset, marking the current cycle and the
+    /// cycles at which breakpoints were hit.
+    fn render_timeline(state: &TritonVMState, width: usize) -> Line<'static> {
+        let current_cycle = state.vm_state.cycle_count;
+        let furthest_cycle = state.furthest_cycle.max(current_cycle);
+        let header = Span::from("Cycle").bold();
+        let cycles = Span::from(format!(" {current_cycle}/{furthest_cycle} "));
+        let bar_width = width.saturating_sub(header.width() + cycles.width());
+
+        let mut bar = vec![Span::from("─").dim(); bar_width];
+        for &cycle in &state.breakpoint_hit_cycles {
+            let position = Self::timeline_position(cycle, furthest_cycle, bar_width);
+            if let Some(mark) = bar.get_mut(position) {
+                *mark = Span::from("◆").red();
+            }
+        }
+        let position = Self::timeline_position(current_cycle, furthest_cycle, bar_width);
+        if let Some(mark) = bar.get_mut(position) {
+            *mark = Span::from("●").bold();
+        }
+
+        [header, cycles].into_iter().chain(bar).collect()
+    }
+
+    /// The index of the timeline bar's cell corresponding to the given cycle.
+    fn timeline_position(cycle: u32, furthest_cycle: u32, bar_width: usize) -> usize {
+        let Some(last_position) = bar_width.checked_sub(1) else {
+            return 0;
+        };
+        let position = u64::from(cycle) * last_position as u64 / u64::from(furthest_cycle.max(1));
+        position as usize
+    }
+
+    fn render_command_line_widget(&mut self, frame: &mut Frame<'_>, render_info: RenderInfo) {
+        self.command_line
+            .set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
+        let title = match self.input_request {
+            Some(input_request) => format!(" Input – {input_request} "),
+            None => " Command ".to_string(),
+        };
+        let block = Block::default()
+            .padding(Padding::horizontal(1))
+            .title(Span::from(title).bold())
+            .title_position(Position::Bottom)
+            .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            .border_type(BorderType::Rounded);
+        self.command_line.set_block(block);
+        frame.render_widget(&self.command_line, render_info.areas.message_box);
+    }
+
     fn render_message_widget(&self, frame: &mut Frame<'_>, render_info: RenderInfo) {
         let message = self.message(render_info.state);
         let status = if render_info.state.vm_state.halting {
@@ -478,7 +803,12 @@ impl Home {
 
     fn message(&self, state: &TritonVMState) -> Line<'_> {
         self.maybe_render_error_message(state)
+            .or_else(|| self.maybe_render_watchpoint_message(state))
+            .or_else(|| self.maybe_render_catchpoint_message(state))
+            .or_else(|| self.maybe_render_input_request_message(state))
             .or_else(|| self.maybe_render_warning_message(state))
+            .or_else(|| self.maybe_render_proving_message(state))
+            .or_else(|| self.maybe_render_info_message(state))
             .or_else(|| self.maybe_render_public_output(state))
             .unwrap_or_else(|| self.render_welcome_message())
     }
@@ -490,6 +820,37 @@ impl Home {
         Some(error + colon + message)
     }
 
+    fn maybe_render_watchpoint_message(&self, state: &TritonVMState) -> Option<Line<'_>> {
+        let (first_hit, other_hits) = state.watchpoint_hits.split_first()?;
+        let watchpoint = "WATCHPOINT".bold().magenta();
+        let colon = ": ".into();
+        let message = Span::from(first_hit.to_string());
+        let mut line = watchpoint + colon + message;
+        if !other_hits.is_empty() {
+            line.push_span(format!(" (and {} more)", other_hits.len()).dim());
+        }
+        Some(line)
+    }
+
+    fn maybe_render_catchpoint_message(&self, state: &TritonVMState) -> Option<Line<'_>> {
+        let catchpoint = state.catchpoint_hit.as_ref()?;
+        let message = match catchpoint {
+            Catchpoint::Call(label) => format!("entered “{label}”"),
+            _ => format!("caught “{catchpoint}”"),
+        };
+        let header = "CATCHPOINT".bold().magenta();
+        let colon = ": ".into();
+        Some(header + colon + Span::from(message))
+    }
+
+    fn maybe_render_input_request_message(&self, state: &TritonVMState) -> Option<Line<'_>> {
+        let input_request = state.input_request?;
+        let message = format!("{input_request}; execute again to enter them");
+        let header = "INPUT".bold().cyan();
+        let colon = ": ".into();
+        Some(header + colon + Span::from(message))
+    }
+
     fn maybe_render_warning_message(&self, state: &TritonVMState) -> Option<Line<'_>> {
         let message = Span::from(state.warning.as_ref()?.to_string());
         let warning = "WARNING".bold().yellow();
@@ -497,6 +858,28 @@ impl Home {
         Some(warning + colon + message)
     }
 
+    fn maybe_render_proving_message(&self, state: &TritonVMState) -> Option<Line<'_>> {
+        const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+        let ProverStatus::Running { phase, start, .. } = state.prover_status else {
+            return None;
+        };
+        let elapsed = start.elapsed();
+        let spinner = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+        let elapsed = elapsed.as_secs_f64();
+        let message = Span::from(format!("{spinner} {phase}… {elapsed:.1}s"));
+        let proving = "PROVING".bold().cyan();
+        let colon = ": ".into();
+        Some(proving + colon + message)
+    }
+
+    fn maybe_render_info_message(&self, state: &TritonVMState) -> Option<Line<'_>> {
+        let message = Span::from(state.info.clone()?);
+        let info = "INFO".bold().green();
+        let colon = ": ".into();
+        Some(info + colon + message)
+    }
+
     fn maybe_render_public_output(&self, state: &TritonVMState) -> Option<Line<'_>> {
         if state.vm_state.public_output.is_empty() {
             return None;
@@ -517,16 +900,58 @@ impl Home {
 }
 
 impl Component for Home {
+    fn request_exclusive_key_event_handling(&self) -> bool {
+        self.command_line_in_focus
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<Action>> {
+        if key_event.kind == KeyEventKind::Release {
+            return Ok(None);
+        }
+        if self.command_line_in_focus {
+            return Ok(self.handle_command_line_key_event(key_event));
+        }
+        if key_event.code == KeyCode::Enter {
+            self.command_line_in_focus = true;
+            return Ok(None);
+        }
+        self.move_cursor(key_event);
+        Ok(None)
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Toggle(toggle) => self.toggle_widget(toggle),
-            Action::Reset => self.rendered_program = None,
+            Action::ToggleBreakpoint => {
+                let address = self.cursor_address();
+                return Ok(Some(Action::ToggleBreakpointAt(address)));
+            }
+            Action::RunToCursor => {
+                let location = Location::Address(self.cursor_address());
+                return Ok(Some(Action::Execute(Execute::RunTo(location))));
+            }
+            Action::Execute(_) | Action::RestoreCheckpoint(_) => self.cursor = None,
+            Action::Mode(mode) if mode != Mode::Home => {
+                self.command_line_in_focus = false;
+                self.input_request = None;
+            }
+            Action::RequestInput(input_request) => {
+                self.input_request = Some(input_request);
+                self.command_line = Self::initial_command_line();
+                self.command_line_in_focus = true;
+                return Ok(Some(Action::Mode(Mode::Home)));
+            }
+            Action::Reset => {
+                self.cursor = None;
+                self.rendered_program = None;
+            }
             _ => (),
         }
         Ok(None)
     }
 
     fn draw(&mut self, frame: &mut Frame<'_>, state: &TritonVMState) -> Result<()> {
+        self.instruction_pointer = state.vm_state.instruction_pointer;
         self.rendered_program
             .get_or_insert_with(|| Self::render_program(&state.vm_state.program));
 
@@ -539,10 +964,16 @@ impl Component for Home {
         self.render_type_hint_widget(frame, render_info);
         self.render_program_widget(frame, render_info);
         self.render_call_stack_widget(frame, render_info);
+        self.render_table_heights_widget(frame, render_info);
         self.render_sponge_widget(frame, render_info);
         self.render_public_input_widget(frame, render_info);
         self.render_secret_input_widget(frame, render_info);
-        self.render_message_widget(frame, render_info);
+        self.render_timeline_widget(frame, render_info);
+        if self.command_line_in_focus {
+            self.render_command_line_widget(frame, render_info);
+        } else {
+            self.render_message_widget(frame, render_info);
+        }
         Ok(())
     }
 }
@@ -559,14 +990,16 @@ struct WidgetAreas {
     type_hint: Rect,
     program: Rect,
     call_stack: Rect,
+    table_heights: Rect,
     sponge: Rect,
     public_input: Rect,
     secret_input: Rect,
+    timeline: Rect,
     message_box: Rect,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Arbitrary)]
-enum ProgramLine {
+pub(crate) enum ProgramLine {
     Label(String),
     Instruction {
         address: usize,
@@ -681,6 +1114,76 @@ mod tests {
         assert_eq!(None, Home::line_index_of_address(&lines, 3));
     }
 
+    #[test]
+    fn toggling_breakpoint_and_running_target_instruction_under_program_cursor() {
+        let program = triton_program!(push 1 pop 1 nop halt);
+        let mut home = Home {
+            rendered_program: Some(Home::render_program(&program)),
+            ..Home::default()
+        };
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        home.handle_key_event(down).unwrap();
+        home.handle_key_event(down).unwrap();
+
+        let action = home.update(Action::ToggleBreakpoint).unwrap();
+        assert_eq!(Some(Action::ToggleBreakpointAt(4)), action);
+
+        let action = home.update(Action::RunToCursor).unwrap();
+        let location = Location::Address(4);
+        assert_eq!(Some(Action::Execute(Execute::RunTo(location))), action);
+
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        home.handle_key_event(esc).unwrap();
+        let action = home.update(Action::ToggleBreakpoint).unwrap();
+        assert_eq!(Some(Action::ToggleBreakpointAt(0)), action);
+    }
+
+    #[test]
+    fn submitting_command_line_produces_action() {
+        let mut home = Home::default();
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        home.handle_key_event(key(KeyCode::Enter)).unwrap();
+        assert!(home.request_exclusive_key_event_handling());
+
+        for c in "clear 0".chars() {
+            home.handle_key_event(key(KeyCode::Char(c))).unwrap();
+        }
+        let action = home.handle_key_event(key(KeyCode::Enter)).unwrap();
+        let expected = Action::DeleteBreakpoint(crate::command::Location::Address(0));
+        assert_eq!(Some(expected), action);
+        assert!(!home.request_exclusive_key_event_handling());
+
+        home.handle_key_event(key(KeyCode::Enter)).unwrap();
+        home.handle_key_event(key(KeyCode::Char('x'))).unwrap();
+        let action = home.handle_key_event(key(KeyCode::Enter)).unwrap();
+        assert!(matches!(action, Some(Action::Error(_))));
+    }
+
+    #[test]
+    fn requested_input_is_entered_on_the_command_line() {
+        let mut home = Home::default();
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let read_io = Instruction::ReadIo(triton_vm::isa::op_stack::NumberOfWords::N2);
+        let input_request = InputRequest::for_instruction(read_io, 0, 0).unwrap();
+        let action = home.update(Action::RequestInput(input_request)).unwrap();
+        assert_eq!(Some(Action::Mode(Mode::Home)), action);
+        home.update(Action::Mode(Mode::Home)).unwrap();
+        assert!(home.request_exclusive_key_event_handling());
+
+        for c in "1 2".chars() {
+            home.handle_key_event(key(KeyCode::Char(c))).unwrap();
+        }
+        let action = home.handle_key_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(Some(Action::EnterInput(bfe_vec![1, 2])), action);
+
+        home.handle_key_event(key(KeyCode::Enter)).unwrap();
+        for c in "prove".chars() {
+            home.handle_key_event(key(KeyCode::Char(c))).unwrap();
+        }
+        let action = home.handle_key_event(key(KeyCode::Enter)).unwrap();
+        assert_eq!(Some(Action::Prove), action);
+    }
+
     #[test]
     fn line_indices_in_rendered_program_containing_only_labels_can_never_be_found() {
         let lines = vec![
@@ -693,4 +1196,39 @@ mod tests {
         assert_eq!(None, Home::line_index_of_address(&lines, 1));
         assert_eq!(None, Home::line_index_of_address(&lines, 2));
     }
+
+    #[test]
+    fn table_heights_warn_about_padded_height_growing() {
+        let program = triton_program!(
+            push 200 call count_down halt
+            count_down: dup 0 push 0 eq skiz return push -1 add recurse
+        );
+        let vm_state = VMState::new(program, [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+
+        let is_warning = |line: &Line| line.to_string().contains("padded height to 512");
+        state.update(Action::Execute(Execute::Step(255))).unwrap();
+        assert!(!Home::render_table_heights(&state).iter().any(is_warning));
+
+        state.update(Action::Execute(Execute::Step(1))).unwrap();
+        assert_eq!(256, state.table_heights.padded_height());
+        assert!(Home::render_table_heights(&state).iter().any(is_warning));
+    }
+
+    #[test]
+    fn heat_colors_range_from_cold_to_hot() {
+        assert_eq!(Color::DarkGray, Home::heat_color(0, 1000));
+        assert_eq!(Color::Blue, Home::heat_color(1, 1_000_000));
+        assert_eq!(Color::Red, Home::heat_color(1000, 1000));
+        assert_eq!(Color::Red, Home::heat_color(1, 1));
+    }
+
+    #[test]
+    fn timeline_positions_span_the_whole_bar() {
+        assert_eq!(0, Home::timeline_position(0, 1000, 50));
+        assert_eq!(24, Home::timeline_position(500, 1000, 50));
+        assert_eq!(49, Home::timeline_position(1000, 1000, 50));
+        assert_eq!(0, Home::timeline_position(0, 0, 50));
+        assert_eq!(0, Home::timeline_position(7, 10, 0));
+    }
 }
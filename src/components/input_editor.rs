@@ -0,0 +1,295 @@
+use color_eyre::eyre::Result;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use ratatui::prelude::*;
+use ratatui::widgets::Block;
+use ratatui::widgets::BorderType;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Padding;
+use ratatui::widgets::Paragraph;
+use triton_vm::prelude::BFieldElement;
+use tui_textarea::TextArea;
+
+use crate::action::Action;
+use crate::action::InputEdit;
+use crate::components::Component;
+use crate::triton_vm_state::TritonVMState;
+
+/// A panel for changing the public input the program has not read yet.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct InputEditor<'a> {
+    /// The selected element.
+    cursor: usize,
+
+    /// The pending public input as most recently rendered.
+    rendered_elements: Vec<BFieldElement>,
+
+    /// What the text typed into the prompt is for, if the prompt is active.
+    prompt: Option<Prompt>,
+    text_area: TextArea<'a>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Prompt {
+    Append,
+    Modify(usize),
+    Save,
+}
+
+impl InputEditor<'_> {
+    const PANEL_WIDTH: u16 = 60;
+    const PANEL_HEIGHT: u16 = 20;
+
+    fn panel_area(area: Rect) -> Rect {
+        let [_, area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(Self::PANEL_WIDTH),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(Self::PANEL_HEIGHT),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        area
+    }
+
+    fn open_prompt(&mut self, prompt: Prompt, text: String) {
+        self.prompt = Some(prompt);
+        self.text_area = TextArea::new(vec![text]);
+        self.text_area.move_cursor(tui_textarea::CursorMove::End);
+    }
+
+    fn handle_prompt_key_event(&mut self, key_event: KeyEvent) -> Option<Action> {
+        match key_event.code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Enter => {
+                let prompt = self.prompt.take()?;
+                let text = self.text_area.lines()[0].trim().to_string();
+                return Self::submit(prompt, &text);
+            }
+            _ => _ = self.text_area.input(key_event),
+        }
+        None
+    }
+
+    fn submit(prompt: Prompt, text: &str) -> Option<Action> {
+        if text.is_empty() {
+            return None;
+        }
+        if prompt == Prompt::Save {
+            return Some(Action::SavePublicInput(text.to_string()));
+        }
+        let elements = match TritonVMState::parse_public_input(text) {
+            Ok(elements) => elements,
+            Err(report) => return Some(Action::Error(format!("invalid input “{text}”: {report}"))),
+        };
+        let edit = match (prompt, elements.as_slice()) {
+            (Prompt::Modify(index), &[element]) => InputEdit::Modify(index, element),
+            (Prompt::Modify(_), _) => {
+                return Some(Action::Error("expected exactly one element".to_string()));
+            }
+            _ => InputEdit::Append(elements),
+        };
+        Some(Action::EditPublicInput(edit))
+    }
+
+    fn handle_list_key_event(&mut self, key_event: KeyEvent) -> Option<Action> {
+        let selected_element = self.rendered_elements.get(self.cursor).copied();
+        match key_event.code {
+            KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down => self.cursor = self.cursor.saturating_add(1),
+            KeyCode::Char('a') => self.open_prompt(Prompt::Append, String::new()),
+            KeyCode::Char('w') => self.open_prompt(Prompt::Save, String::new()),
+            KeyCode::Enter | KeyCode::Char('e') => {
+                let element = selected_element?;
+                self.open_prompt(Prompt::Modify(self.cursor), element.to_string());
+            }
+            KeyCode::Char('x') | KeyCode::Delete => {
+                selected_element?;
+                return Some(Action::EditPublicInput(InputEdit::Delete(self.cursor)));
+            }
+            _ => (),
+        }
+        None
+    }
+
+    fn element_lines(&self, height: usize) -> Vec<Line<'static>> {
+        if self.rendered_elements.is_empty() {
+            let hint = "No pending public input. Press “a” to append some.";
+            return vec![Line::from(hint).dim()];
+        }
+        let first_visible_line = self.cursor.saturating_sub(height / 2);
+        self.rendered_elements
+            .iter()
+            .enumerate()
+            .skip(first_visible_line)
+            .take(height)
+            .map(|(index, element)| {
+                let line = Line::from(format!("{index:>5}  {element}"));
+                if index == self.cursor {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    fn render_prompt(&mut self, frame: &mut Frame<'_>, area: Rect) {
+        let Some(prompt) = self.prompt else {
+            return;
+        };
+        let prompt = match prompt {
+            Prompt::Append => "append: ".to_string(),
+            Prompt::Modify(index) => format!("modify {index}: "),
+            Prompt::Save => "save to file: ".to_string(),
+        };
+        let prompt_width = u16::try_from(prompt.len()).unwrap_or(u16::MAX);
+        let [prompt_area, text_area] =
+            Layout::horizontal([Constraint::Length(prompt_width), Constraint::Fill(1)]).areas(area);
+        frame.render_widget(Span::from(prompt).bold(), prompt_area);
+        self.text_area.set_cursor_line_style(Style::default());
+        frame.render_widget(&self.text_area, text_area);
+    }
+
+    fn message(state: &TritonVMState) -> Line<'static> {
+        if let Some(ref warning) = state.warning {
+            return "WARNING".bold().yellow() + Span::from(format!(": {warning}"));
+        }
+        if let Some(ref info) = state.info {
+            return "INFO".bold().green() + Span::from(format!(": {info}"));
+        }
+        Line::default()
+    }
+}
+
+impl Component for InputEditor<'_> {
+    fn request_exclusive_key_event_handling(&self) -> bool {
+        self.prompt.is_some()
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<Action>> {
+        if key_event.kind == KeyEventKind::Release {
+            return Ok(None);
+        }
+        let action = match self.prompt {
+            Some(_) => self.handle_prompt_key_event(key_event),
+            None => self.handle_list_key_event(key_event),
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Mode(_) => self.prompt = None,
+            Action::Reset => *self = Self::default(),
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, state: &TritonVMState) -> Result<()> {
+        self.rendered_elements = state.vm_state.public_input.iter().copied().collect();
+        self.cursor = self
+            .cursor
+            .min(self.rendered_elements.len().saturating_sub(1));
+
+        let area = Self::panel_area(frame.area());
+        let block = Block::default()
+            .padding(Padding::new(1, 1, 1, 0))
+            .title(" Public Input ".bold())
+            .title_bottom(" a append  e edit  x delete  w save  esc back ".dim())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded);
+        let inner_area = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let [list_area, prompt_area, message_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner_area);
+        let lines = self.element_lines(list_area.height.into());
+        frame.render_widget(Paragraph::new(lines), list_area);
+        self.render_prompt(frame, prompt_area);
+        frame.render_widget(Self::message(state), message_area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+    use crossterm::event::KeyModifiers;
+    use ratatui::backend::TestBackend;
+    use triton_vm::prelude::*;
+
+    use crate::action::Execute;
+    use crate::args::TuiArgs;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(editor: &mut InputEditor, text: &str) {
+        for char in text.chars() {
+            let_assert!(Ok(None) = editor.handle_key_event(key(KeyCode::Char(char))));
+        }
+    }
+
+    fn draw(editor: &mut InputEditor, state: &TritonVMState) {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal
+            .draw(|frame| editor.draw(frame, state).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn edited_input_is_read_by_the_program() {
+        let program = triton_program!(read_io 1 read_io 1 add write_io 1 halt);
+        let vm_state = VMState::new(program, bfe_vec![1, 2].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        let mut editor = InputEditor::default();
+        draw(&mut editor, &state);
+
+        let_assert!(Ok(None) = editor.handle_key_event(key(KeyCode::Char('e'))));
+        assert!(editor.request_exclusive_key_event_handling());
+        let_assert!(Ok(None) = editor.handle_key_event(key(KeyCode::Backspace)));
+        type_text(&mut editor, "40");
+        let_assert!(Ok(Some(action)) = editor.handle_key_event(key(KeyCode::Enter)));
+        assert!(Action::EditPublicInput(InputEdit::Modify(0, bfe!(40))) == action);
+        state.update(action).unwrap();
+
+        let_assert!(Ok(None) = editor.handle_key_event(key(KeyCode::Down)));
+        let_assert!(Ok(Some(action)) = editor.handle_key_event(key(KeyCode::Delete)));
+        state.update(action).unwrap();
+        draw(&mut editor, &state);
+        let_assert!(Ok(None) = editor.handle_key_event(key(KeyCode::Char('a'))));
+        type_text(&mut editor, "2 7");
+        let_assert!(Ok(Some(action)) = editor.handle_key_event(key(KeyCode::Enter)));
+        state.update(action).unwrap();
+        assert!(bfe_vec![40, 2, 7] == Vec::from(state.vm_state.public_input.clone()));
+
+        state.update(Action::Execute(Execute::Continue(1))).unwrap();
+        assert!(bfe_vec![42] == state.vm_state.public_output);
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let mut editor = InputEditor::default();
+        let_assert!(Ok(None) = editor.handle_key_event(key(KeyCode::Char('a'))));
+        type_text(&mut editor, "1 two");
+        let_assert!(Ok(Some(Action::Error(_))) = editor.handle_key_event(key(KeyCode::Enter)));
+        assert!(!editor.request_exclusive_key_event_handling());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use arbitrary::Arbitrary;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyEventKind::Release;
@@ -9,7 +11,6 @@ use ratatui::widgets::BorderType;
 use ratatui::widgets::Borders;
 use ratatui::widgets::Padding;
 use ratatui::widgets::Paragraph;
-use triton_vm::isa::instruction::Instruction;
 use triton_vm::prelude::*;
 use tui_textarea::TextArea;
 
@@ -34,6 +35,10 @@ pub(crate) struct Memory<'a> {
     pub show_block_addresses: bool,
 
     pub undo_stack: Vec<UndoInformation>,
+    pub redo_stack: Vec<UndoInformation>,
+
+    /// The manually set address to show, per named checkpoint.
+    pub checkpoint_user_addresses: HashMap<String, Option<BFieldElement>>,
 }
 
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Arbitrary)]
@@ -62,6 +67,8 @@ impl Default for Memory<'_> {
             text_area_in_focus: false,
             show_block_addresses: false,
             undo_stack: vec![],
+            redo_stack: vec![],
+            checkpoint_user_addresses: HashMap::new(),
         }
     }
 }
@@ -78,32 +85,42 @@ impl<'a> Memory<'a> {
             return;
         };
 
+        self.redo_stack.push(self.undo_information());
         self.most_recent_address = undo_information.most_recent_address;
     }
 
+    pub fn redo(&mut self) {
+        let Some(redo_information) = self.redo_stack.pop() else {
+            return;
+        };
+
+        self.undo_stack.push(self.undo_information());
+        self.most_recent_address = redo_information.most_recent_address;
+    }
+
     pub fn record_undo_information(&mut self) {
-        let undo_information = UndoInformation {
+        self.undo_stack.push(self.undo_information());
+        self.redo_stack.clear();
+    }
+
+    fn undo_information(&self) -> UndoInformation {
+        UndoInformation {
             most_recent_address: self.most_recent_address,
-        };
-        self.undo_stack.push(undo_information);
+        }
     }
 
     pub fn reset(&mut self) {
         self.most_recent_address = 0_u64.into();
         self.user_address = None;
         self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.checkpoint_user_addresses.clear();
     }
 
     pub fn handle_instruction(&mut self, executed_instruction: ExecutedInstruction) {
-        let overshoot_adjustment = match executed_instruction.instruction {
-            Instruction::ReadMem(_) => bfe!(1),
-            Instruction::WriteMem(_)
-            | Instruction::SpongeAbsorbMem
-            | Instruction::XbDotStep
-            | Instruction::XxDotStep => bfe!(-1),
-            _ => return, // instruction does not access memory
-        };
-        self.most_recent_address = executed_instruction.new_top_of_stack[0] + overshoot_adjustment;
+        if let Some(access) = executed_instruction.ram_accesses().last() {
+            self.most_recent_address = access.address;
+        }
     }
 
     pub fn toggle_address_display(&mut self) {
@@ -351,8 +368,20 @@ impl Component for Memory<'_> {
         match action {
             Action::Mode(_) => self.text_area_in_focus = false,
             Action::Undo => self.undo(),
+            Action::Redo => self.redo(),
             Action::RecordUndoInfo => self.record_undo_information(),
+            Action::Rewound(address) => self.most_recent_address = address,
             Action::Reset => self.reset(),
+            Action::SaveCheckpoint(name) => {
+                self.checkpoint_user_addresses
+                    .insert(name, self.user_address);
+            }
+            Action::RestoreCheckpoint(name) => {
+                if let Some(&user_address) = self.checkpoint_user_addresses.get(&name) {
+                    self.user_address = user_address;
+                }
+            }
+            Action::DeleteCheckpoint(name) => _ = self.checkpoint_user_addresses.remove(&name),
             Action::ExecutedInstruction(instruction) => self.handle_instruction(*instruction),
             Action::Toggle(Toggle::BlockAddress) => self.toggle_address_display(),
             _ => (),
@@ -375,9 +404,12 @@ impl Component for Memory<'_> {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prop_assert_eq;
     use proptest_arbitrary_interop::arb;
     use ratatui::backend::TestBackend;
     use test_strategy::proptest;
+    use triton_vm::isa::instruction::Instruction;
+    use triton_vm::isa::op_stack::NumberOfWords;
     use triton_vm::prelude::*;
 
     use crate::args::TuiArgs;
@@ -399,6 +431,9 @@ mod tests {
 
         #[strategy(arb())]
         undo_stack: Vec<UndoInformation>,
+
+        #[strategy(arb())]
+        redo_stack: Vec<UndoInformation>,
     }
 
     #[proptest]
@@ -413,6 +448,8 @@ mod tests {
             text_area_in_focus: arb_memory.text_area_in_focus,
             show_block_addresses: arb_memory.show_block_addresses,
             undo_stack: arb_memory.undo_stack,
+            redo_stack: arb_memory.redo_stack,
+            checkpoint_user_addresses: HashMap::new(),
         };
 
         let mut complete_state = TritonVMState::new(&TuiArgs::default()).unwrap();
@@ -424,4 +461,51 @@ mod tests {
             .draw(|f| memory.draw(f, &complete_state).unwrap())
             .unwrap();
     }
+
+    #[test]
+    fn redo_restores_most_recent_address_until_new_undo_information_is_recorded() {
+        let mut memory = Memory::default();
+        memory.record_undo_information();
+        memory.most_recent_address = bfe!(42);
+
+        memory.undo();
+        assert_eq!(bfe!(0), memory.most_recent_address);
+        memory.redo();
+        assert_eq!(bfe!(42), memory.most_recent_address);
+
+        memory.undo();
+        memory.record_undo_information();
+        assert!(memory.redo_stack.is_empty());
+    }
+
+    #[proptest]
+    fn most_recent_address_is_last_address_accessed_by_instruction(
+        #[strategy(arb())] mut executed_instruction: ExecutedInstruction,
+    ) {
+        let overshoot_adjustment = match executed_instruction.instruction {
+            Instruction::ReadMem(_) => bfe!(1),
+            Instruction::WriteMem(_)
+            | Instruction::SpongeAbsorbMem
+            | Instruction::XbDotStep
+            | Instruction::XxDotStep => bfe!(-1),
+            _ => return Ok(()),
+        };
+
+        // `new_top_of_stack` is arbitrary; make it consistent with the instruction
+        let old_pointer = executed_instruction.old_top_of_stack[0];
+        let num_words = |n: NumberOfWords| bfe!(n.num_words() as u64);
+        executed_instruction.new_top_of_stack[0] = match executed_instruction.instruction {
+            Instruction::ReadMem(n) => old_pointer - num_words(n),
+            Instruction::WriteMem(n) => old_pointer + num_words(n),
+            Instruction::SpongeAbsorbMem => old_pointer + bfe!(tip5::RATE as u64),
+            Instruction::XxDotStep => old_pointer + bfe!(3),
+            Instruction::XbDotStep => old_pointer + bfe!(1),
+            _ => unreachable!(),
+        };
+        let expected_address = executed_instruction.new_top_of_stack[0] + overshoot_adjustment;
+
+        let mut memory = Memory::default();
+        memory.handle_instruction(executed_instruction);
+        prop_assert_eq!(expected_address, memory.most_recent_address);
+    }
 }
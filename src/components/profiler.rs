@@ -0,0 +1,398 @@
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+
+use arbitrary::Arbitrary;
+use color_eyre::eyre::Result;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use itertools::Itertools;
+use ratatui::prelude::*;
+use ratatui::widgets::Block;
+use ratatui::widgets::BorderType;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Padding;
+use ratatui::widgets::Paragraph;
+
+use crate::action::Action;
+use crate::components::Component;
+use crate::profile::FunctionSummary;
+use crate::profile::Profile;
+use crate::triton_vm_state::TritonVMState;
+
+/// Shows where execution spends its cycles: a table of all functions, and the call tree.
+#[derive(Debug, Clone, Arbitrary)]
+pub(crate) struct Profiler {
+    sort_column: SortColumn,
+
+    /// Whether the cursor keys move within the call tree, as opposed to the function table.
+    call_tree_in_focus: bool,
+
+    /// The selected row of the function table.
+    table_cursor: usize,
+
+    /// The selected line of the call tree. Indexes the currently visible nodes only.
+    call_tree_cursor: usize,
+
+    /// The call tree nodes whose children are shown.
+    expanded_nodes: BTreeSet<usize>,
+
+    /// The call tree nodes as most recently rendered, top to bottom.
+    rendered_call_tree_nodes: Vec<usize>,
+}
+
+/// The column by which the function table is sorted.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Arbitrary)]
+enum SortColumn {
+    #[default]
+    InclusiveCycles,
+    ExclusiveCycles,
+    NumCalls,
+    Label,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct WidgetAreas {
+    function_table: Rect,
+    call_tree: Rect,
+}
+
+/// A line of the call tree, referring to a node of the [`Profile`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct CallTreeLine {
+    node: usize,
+    depth: usize,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self {
+            sort_column: SortColumn::default(),
+            call_tree_in_focus: false,
+            table_cursor: 0,
+            call_tree_cursor: 0,
+            expanded_nodes: BTreeSet::from([Profile::ROOT]),
+            rendered_call_tree_nodes: vec![],
+        }
+    }
+}
+
+impl SortColumn {
+    fn next(self) -> Self {
+        match self {
+            Self::InclusiveCycles => Self::ExclusiveCycles,
+            Self::ExclusiveCycles => Self::NumCalls,
+            Self::NumCalls => Self::Label,
+            Self::Label => Self::InclusiveCycles,
+        }
+    }
+
+    fn sort(self, summaries: &mut [FunctionSummary]) {
+        match self {
+            Self::InclusiveCycles => summaries.sort_by_key(|s| Reverse(s.inclusive_cycles)),
+            Self::ExclusiveCycles => summaries.sort_by_key(|s| Reverse(s.exclusive_cycles)),
+            Self::NumCalls => summaries.sort_by_key(|s| Reverse(s.num_calls)),
+            Self::Label => summaries.sort_by(|a, b| a.label.cmp(&b.label)),
+        }
+    }
+}
+
+impl Profiler {
+    fn reset(&mut self) {
+        *self = Self {
+            sort_column: self.sort_column,
+            ..Self::default()
+        };
+    }
+
+    fn move_cursor(&mut self, key: KeyEvent) {
+        let page_size = 20;
+        let cursor = if self.call_tree_in_focus {
+            &mut self.call_tree_cursor
+        } else {
+            &mut self.table_cursor
+        };
+        *cursor = match key.code {
+            KeyCode::Up => cursor.saturating_sub(1),
+            KeyCode::Down => cursor.saturating_add(1),
+            KeyCode::PageUp => cursor.saturating_sub(page_size),
+            KeyCode::PageDown => cursor.saturating_add(page_size),
+            KeyCode::Home => 0,
+            _ => *cursor,
+        };
+    }
+
+    /// Expand or collapse the call tree node under the cursor.
+    fn set_expansion(&mut self, expand: bool) {
+        let Some(&node) = self.rendered_call_tree_nodes.get(self.call_tree_cursor) else {
+            return;
+        };
+        if expand {
+            self.expanded_nodes.insert(node);
+        } else {
+            self.expanded_nodes.remove(&node);
+        }
+    }
+
+    /// The function summaries in the order of the function table.
+    fn sorted_summaries(&self, state: &TritonVMState) -> Vec<FunctionSummary> {
+        let mut summaries = state.profile.function_summaries(&state.vm_state.program);
+        summaries.sort_by(|a, b| a.label.cmp(&b.label));
+        self.sort_column.sort(&mut summaries);
+        summaries
+    }
+
+    /// The currently visible lines of the call tree: all nodes whose ancestors are expanded.
+    /// Siblings are ordered by their inclusive cycle count, most expensive first.
+    fn call_tree_lines(&self, profile: &Profile) -> Vec<CallTreeLine> {
+        let inclusive_cycles = profile.inclusive_cycles();
+        let mut lines = vec![];
+        let mut pending = vec![CallTreeLine {
+            node: Profile::ROOT,
+            depth: 0,
+        }];
+        while let Some(line) = pending.pop() {
+            lines.push(line);
+            if !self.expanded_nodes.contains(&line.node) {
+                continue;
+            }
+            let children = profile.nodes()[line.node]
+                .children
+                .iter()
+                .sorted_by_key(|&&child| Reverse(inclusive_cycles[child]))
+                .rev()
+                .map(|&node| CallTreeLine {
+                    node,
+                    depth: line.depth + 1,
+                });
+            pending.extend(children);
+        }
+        lines
+    }
+
+    fn distribute_area_for_widgets(area: Rect) -> WidgetAreas {
+        let [function_table, call_tree] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+        WidgetAreas {
+            function_table,
+            call_tree,
+        }
+    }
+
+    /// The index of the first line to render such that the cursor is visible.
+    fn first_visible_line(cursor: usize, height: usize) -> usize {
+        cursor.saturating_sub(height / 2)
+    }
+
+    fn block(title: String, is_in_focus: bool) -> Block<'static> {
+        let title = if is_in_focus {
+            title.bold()
+        } else {
+            title.into()
+        };
+        Block::default()
+            .padding(Padding::new(1, 1, 1, 0))
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+    }
+
+    fn render_function_table(&mut self, frame: &mut Frame<'_>, state: &TritonVMState, area: Rect) {
+        let sort_column = match self.sort_column {
+            SortColumn::InclusiveCycles => "inclusive cycles",
+            SortColumn::ExclusiveCycles => "exclusive cycles",
+            SortColumn::NumCalls => "calls",
+            SortColumn::Label => "label",
+        };
+        let title = format!(" Functions (by {sort_column}) ");
+        let block = Self::block(title, !self.call_tree_in_focus);
+
+        let summaries = self.sorted_summaries(state);
+        self.table_cursor = self.table_cursor.min(summaries.len().saturating_sub(1));
+        let label_width = summaries.iter().map(|s| s.label.len()).max().unwrap_or(0);
+
+        let header = format!(
+            "{:<label_width$}  {:>9}  {:>11}  {:>11}",
+            "function", "calls", "inclusive", "exclusive"
+        );
+        let mut text = vec![Line::from(header).bold()];
+        let height = usize::from(block.inner(area).height.saturating_sub(1));
+        let first_line = Self::first_visible_line(self.table_cursor, height);
+        for (row, summary) in summaries.iter().enumerate().skip(first_line).take(height) {
+            let line = format!(
+                "{:<label_width$}  {:>9}  {:>11}  {:>11}",
+                summary.label,
+                summary.num_calls,
+                summary.inclusive_cycles,
+                summary.exclusive_cycles,
+            );
+            let line = Line::from(line);
+            let line = if row == self.table_cursor && !self.call_tree_in_focus {
+                line.reversed()
+            } else {
+                line
+            };
+            text.push(line);
+        }
+
+        frame.render_widget(Paragraph::new(text).block(block), area);
+    }
+
+    fn render_call_tree(&mut self, frame: &mut Frame<'_>, state: &TritonVMState, area: Rect) {
+        let block = Self::block(" Call Tree ".to_string(), self.call_tree_in_focus);
+
+        let profile = &state.profile;
+        let program = &state.vm_state.program;
+        let inclusive_cycles = profile.inclusive_cycles();
+        let total_cycles = inclusive_cycles[Profile::ROOT].max(1);
+        let lines = self.call_tree_lines(profile);
+        self.call_tree_cursor = self.call_tree_cursor.min(lines.len().saturating_sub(1));
+        self.rendered_call_tree_nodes = lines.iter().map(|line| line.node).collect();
+
+        let height = usize::from(block.inner(area).height);
+        let first_line = Self::first_visible_line(self.call_tree_cursor, height);
+        let mut text = vec![];
+        for (index, line) in lines.iter().enumerate().skip(first_line).take(height) {
+            let node = &profile.nodes()[line.node];
+            let marker = match (
+                node.children.is_empty(),
+                self.expanded_nodes.contains(&line.node),
+            ) {
+                (true, _) => " ",
+                (false, true) => "▾",
+                (false, false) => "▸",
+            };
+            let indentation = "  ".repeat(line.depth);
+            let label = program.label_for_address(node.address);
+            let cycles = inclusive_cycles[line.node];
+            let percentage = 100 * u64::from(cycles) / u64::from(total_cycles);
+            let rendered_line = Line::from(vec![
+                format!("{indentation}{marker} {label}").into(),
+                format!("  {cycles} cycles ({percentage}%)").dim(),
+                format!("  ×{}", node.num_calls).dim(),
+            ]);
+            let rendered_line = if index == self.call_tree_cursor && self.call_tree_in_focus {
+                rendered_line.reversed()
+            } else {
+                rendered_line
+            };
+            text.push(rendered_line);
+        }
+
+        frame.render_widget(Paragraph::new(text).block(block), area);
+    }
+}
+
+impl Component for Profiler {
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<Action>> {
+        if key_event.kind == KeyEventKind::Release || key_event.modifiers != KeyModifiers::NONE {
+            return Ok(None);
+        }
+        match key_event.code {
+            KeyCode::Tab => self.call_tree_in_focus = !self.call_tree_in_focus,
+            KeyCode::Char('o') => self.sort_column = self.sort_column.next(),
+            KeyCode::Right | KeyCode::Enter if self.call_tree_in_focus => self.set_expansion(true),
+            KeyCode::Left if self.call_tree_in_focus => self.set_expansion(false),
+            _ => self.move_cursor(key_event),
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if action == Action::Reset {
+            self.reset();
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>, state: &TritonVMState) -> Result<()> {
+        let areas = Self::distribute_area_for_widgets(frame.area());
+        self.render_function_table(frame, state, areas.function_table);
+        self.render_call_tree(frame, state, areas.call_tree);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use proptest_arbitrary_interop::arb;
+    use ratatui::backend::TestBackend;
+    use test_strategy::proptest;
+    use triton_vm::prelude::*;
+
+    use crate::action::Execute;
+    use crate::args::TuiArgs;
+
+    use super::*;
+
+    fn state_after_running(program: Program) -> TritonVMState {
+        let vm_state = VMState::new(program, [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        state.update(Action::Execute(Execute::Continue(1))).unwrap();
+        state
+    }
+
+    #[test]
+    fn function_table_can_be_sorted_by_any_column() {
+        let program = triton_program!(
+            call foo call bar call bar halt
+            foo: nop nop nop nop nop return
+            bar: nop return
+        );
+        let state = state_after_running(program);
+        let mut profiler = Profiler::default();
+        let labels = |profiler: &Profiler| {
+            let summaries = profiler.sorted_summaries(&state);
+            summaries.into_iter().map(|s| s.label).collect_vec()
+        };
+
+        assert!(["address_0", "foo", "bar"] == labels(&profiler)[..]);
+        profiler.sort_column = SortColumn::NumCalls;
+        assert!("bar" == labels(&profiler)[0]);
+        profiler.sort_column = SortColumn::Label;
+        assert!(["address_0", "bar", "foo"] == labels(&profiler)[..]);
+    }
+
+    #[test]
+    fn collapsed_call_tree_nodes_hide_their_children() {
+        let program = triton_program!(call foo halt foo: call bar return bar: return);
+        let state = state_after_running(program);
+        let mut profiler = Profiler {
+            call_tree_in_focus: true,
+            ..Profiler::default()
+        };
+        let render = |profiler: &mut Profiler| {
+            let mut terminal = Terminal::new(TestBackend::new(150, 50)).unwrap();
+            terminal
+                .draw(|f| profiler.draw(f, &state).unwrap())
+                .unwrap();
+            profiler.rendered_call_tree_nodes.len()
+        };
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert!(2 == render(&mut profiler));
+
+        profiler.handle_key_event(key(KeyCode::Down)).unwrap();
+        profiler.handle_key_event(key(KeyCode::Right)).unwrap();
+        assert!(3 == render(&mut profiler));
+        let lines = profiler.call_tree_lines(&state.profile);
+        assert!(2 == lines[2].depth);
+
+        profiler.handle_key_event(key(KeyCode::Up)).unwrap();
+        profiler.handle_key_event(key(KeyCode::Left)).unwrap();
+        assert!(1 == render(&mut profiler));
+    }
+
+    #[proptest]
+    fn render(#[strategy(arb())] mut profiler: Profiler) {
+        let program = triton_program!(call foo halt foo: push 1 pop 1 return);
+        let state = state_after_running(program);
+
+        let backend = TestBackend::new(150, 50);
+        let mut terminal = Terminal::new(backend)?;
+        terminal
+            .draw(|f| profiler.draw(f, &state).unwrap())
+            .unwrap();
+    }
+}
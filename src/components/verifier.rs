@@ -0,0 +1,127 @@
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use ratatui::prelude::*;
+use ratatui::widgets::Block;
+use ratatui::widgets::BorderType;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Padding;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Wrap;
+
+use crate::components::Component;
+use crate::triton_vm_state::TritonVMState;
+use crate::verifier::Verification;
+
+/// Shows the claim of a loaded proof and whether the proof is valid.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct Verifier {
+    verification: Option<Verification>,
+}
+
+impl Verifier {
+    pub fn new(verification: Option<Verification>) -> Self {
+        Self { verification }
+    }
+
+    fn field(name: &str, value: impl Into<Span<'static>>) -> Line<'static> {
+        let name = Span::from(format!("{name:<16}")).bold();
+        name + value.into()
+    }
+
+    fn render_lines(verification: &Verification) -> Vec<Line<'static>> {
+        let claim = &verification.claim;
+        let proof_size = verification.proof_size as f64 / 1024.0;
+        let padded_height = verification
+            .padded_height
+            .map_or_else(|| "unknown".to_string(), |height| height.to_string());
+        let proof = format!(
+            "{} ({proof_size:.1} KiB, padded height {padded_height})",
+            verification.proof_path
+        );
+
+        let verdict = match verification.failure {
+            None => Span::from("valid").bold().green(),
+            Some(ref failure) => Span::from(format!("INVALID: {failure}")).bold().red(),
+        };
+        let program = match verification.program_digest_matches {
+            Some(true) => Span::from("digest matches the claim").green(),
+            Some(false) => Span::from("digest does NOT match the claim").bold().red(),
+            None => Span::from("none given, digest not checked").dim(),
+        };
+
+        vec![
+            Self::field("Proof", proof),
+            Self::field("Claim", verification.claim_path.clone()),
+            Line::default(),
+            Self::field("Version", claim.version.to_string()),
+            Self::field("Program digest", format!("{:x}", claim.program_digest)),
+            Self::field("Input", format!("[{}]", claim.input.iter().join(", "))),
+            Self::field("Output", format!("[{}]", claim.output.iter().join(", "))),
+            Line::default(),
+            Self::field("Program", program),
+            Self::field("Verdict", verdict),
+        ]
+    }
+}
+
+impl Component for Verifier {
+    fn draw(&mut self, frame: &mut Frame<'_>, _: &TritonVMState) -> Result<()> {
+        let lines = match self.verification {
+            Some(ref verification) => Self::render_lines(verification),
+            None => {
+                let hint = "No proof loaded. Start with “--proof <file> --claim <file>”.";
+                vec![Line::from(hint).dim()]
+            }
+        };
+        let block = Block::default()
+            .padding(Padding::new(1, 1, 1, 0))
+            .title(" Verifier ".bold())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded);
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, frame.area());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use ratatui::backend::TestBackend;
+    use triton_vm::prelude::BFieldElement;
+    use triton_vm::prelude::Claim;
+    use triton_vm::prelude::bfe_vec;
+    use triton_vm::prelude::triton_program;
+
+    use crate::args::TuiArgs;
+
+    use super::*;
+
+    #[test]
+    fn verification_verdict_and_claim_are_shown() {
+        let verification = Verification {
+            proof_path: "proof.json".to_string(),
+            claim_path: "claim.json".to_string(),
+            claim: Claim::about_program(&triton_program!(halt)).with_output(bfe_vec![42]),
+            proof_size: 2048,
+            padded_height: Some(256),
+            failure: Some("out-of-domain rows mismatch".to_string()),
+            program_digest_matches: Some(false),
+        };
+        let mut verifier = Verifier::new(Some(verification));
+        let state = TritonVMState::new(&TuiArgs::default()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal
+            .draw(|frame| verifier.draw(frame, &state).unwrap())
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let screen = buffer.content().iter().map(|cell| cell.symbol()).join("");
+        assert!(screen.contains("proof.json (2.0 KiB, padded height 256)"));
+        assert!(screen.contains("[42]"));
+        assert!(screen.contains("does NOT match"));
+        assert!(screen.contains("INVALID: out-of-domain rows mismatch"));
+    }
+}
@@ -0,0 +1,563 @@
+use std::fmt;
+use std::fmt::Display;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
+use serde::Serialize;
+use serde::Serializer;
+use triton_vm::isa::op_stack::NUM_OP_STACK_REGISTERS;
+use triton_vm::prelude::*;
+
+use crate::triton_vm_state::TritonVMState;
+
+/// A boolean expression over the state of Triton VM, for example `st0 > 1000` or
+/// `loop_counter == 3 && ram[42] != 0`.
+///
+/// The available operands are
+/// - the accessible stack elements `st0` through `st15`,
+/// - RAM cells, `ram[address]`, where `address` is itself an expression,
+/// - the `jump_stack_depth`,
+/// - the `cycle_count`,
+/// - variables named by type hints, `name` or `name[index]` for multi-element types,
+/// - and decimal constants.
+///
+/// Arithmetic (`+`, `-`, `*`) happens in the base field. Comparisons `<`, `<=`, `>`, and `>=`
+/// use the canonical representatives of field elements.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Condition {
+    source: String,
+    expression: Expression,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Expression {
+    Constant(BFieldElement),
+    StackElement(usize),
+    Ram(Box<Expression>),
+    JumpStackDepth,
+    CycleCount,
+    Variable { name: String, index: Option<usize> },
+    Negate(Box<Expression>),
+    Not(Box<Expression>),
+    Binary(Box<Expression>, BinaryOperator, Box<Expression>),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum BinaryOperator {
+    Add,
+    Sub,
+    Mul,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Type {
+    Field,
+    Bool,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Value {
+    Field(BFieldElement),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Token {
+    Number(String),
+    Identifier(String),
+    Operator(&'static str),
+}
+
+impl Condition {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = Self::tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let expression = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            bail!("unexpected “{token}” in condition “{source}”");
+        }
+        if expression.type_of()? != Type::Bool {
+            bail!("condition “{source}” is not a comparison");
+        }
+
+        let source = source.trim().to_string();
+        Ok(Self { source, expression })
+    }
+
+    fn tokenize(source: &str) -> Result<Vec<Token>> {
+        const OPERATORS: [&str; 16] = [
+            "==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "+", "-", "*", "(", ")", "[", "]",
+        ];
+
+        let mut tokens = vec![];
+        let mut chars: Peekable<CharIndices> = source.char_indices().peekable();
+        while let Some(&(start, c)) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+                continue;
+            }
+            if c.is_ascii_alphanumeric() || c == '_' {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = source[start..end].to_string();
+                let token = if c.is_ascii_digit() {
+                    Token::Number(word)
+                } else {
+                    Token::Identifier(word)
+                };
+                tokens.push(token);
+                continue;
+            }
+            let rest = &source[start..];
+            let Some(operator) = OPERATORS.into_iter().find(|op| rest.starts_with(op)) else {
+                bail!("unexpected character “{c}” in condition “{source}”");
+            };
+            for _ in 0..operator.len() {
+                chars.next();
+            }
+            tokens.push(Token::Operator(operator));
+        }
+        Ok(tokens)
+    }
+
+    pub fn evaluate(&self, state: &TritonVMState) -> Result<bool> {
+        match self.expression.evaluate(state)? {
+            Value::Bool(holds) => Ok(holds),
+            Value::Field(_) => unreachable!("type of conditions is checked when parsing"),
+        }
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl Serialize for Condition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(s) | Token::Identifier(s) => write!(f, "{s}"),
+            Token::Operator(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// A recursive descent parser for [`Condition`]s. From lowest to highest precedence, the grammar
+/// is made up of `||`, `&&`, comparisons, `+` and `-`, `*`, and unary `!` and `-`.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn next_is(&mut self, operator: &str) -> bool {
+        let is_match = matches!(self.peek(), Some(Token::Operator(op)) if *op == operator);
+        if is_match {
+            self.position += 1;
+        }
+        is_match
+    }
+
+    fn expect(&mut self, operator: &str) -> Result<()> {
+        match self.next() {
+            Some(Token::Operator(op)) if op == operator => Ok(()),
+            Some(token) => bail!("expected “{operator}” but found “{token}”"),
+            None => bail!("expected “{operator}” but the condition ended"),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expression> {
+        let mut expression = self.parse_and()?;
+        while self.next_is("||") {
+            let rhs = self.parse_and()?;
+            expression = Expression::binary(expression, BinaryOperator::Or, rhs);
+        }
+        Ok(expression)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression> {
+        let mut expression = self.parse_comparison()?;
+        while self.next_is("&&") {
+            let rhs = self.parse_comparison()?;
+            expression = Expression::binary(expression, BinaryOperator::And, rhs);
+        }
+        Ok(expression)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expression> {
+        let lhs = self.parse_sum()?;
+        let operators = [
+            ("==", BinaryOperator::Eq),
+            ("!=", BinaryOperator::Ne),
+            ("<=", BinaryOperator::Le),
+            (">=", BinaryOperator::Ge),
+            ("<", BinaryOperator::Lt),
+            (">", BinaryOperator::Gt),
+        ];
+        for (symbol, operator) in operators {
+            if self.next_is(symbol) {
+                let rhs = self.parse_sum()?;
+                return Ok(Expression::binary(lhs, operator, rhs));
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression> {
+        let mut expression = self.parse_product()?;
+        loop {
+            let operator = if self.next_is("+") {
+                BinaryOperator::Add
+            } else if self.next_is("-") {
+                BinaryOperator::Sub
+            } else {
+                return Ok(expression);
+            };
+            let rhs = self.parse_product()?;
+            expression = Expression::binary(expression, operator, rhs);
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Expression> {
+        let mut expression = self.parse_unary()?;
+        while self.next_is("*") {
+            let rhs = self.parse_unary()?;
+            expression = Expression::binary(expression, BinaryOperator::Mul, rhs);
+        }
+        Ok(expression)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression> {
+        if self.next_is("!") {
+            return Ok(Expression::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.next_is("-") {
+            return Ok(Expression::Negate(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression> {
+        let token = self.next();
+        match token {
+            Some(Token::Number(number)) => {
+                let constant = number
+                    .parse::<BFieldElement>()
+                    .map_err(|err| anyhow!("invalid number “{number}”: {err}"))?;
+                Ok(Expression::Constant(constant))
+            }
+            Some(Token::Identifier(identifier)) => self.parse_identifier(identifier),
+            Some(Token::Operator("(")) => {
+                let expression = self.parse_or()?;
+                self.expect(")")?;
+                Ok(expression)
+            }
+            Some(token) => bail!("unexpected “{token}”"),
+            None => bail!("unexpected end of condition"),
+        }
+    }
+
+    fn parse_identifier(&mut self, identifier: String) -> Result<Expression> {
+        match identifier.as_str() {
+            "jump_stack_depth" => return Ok(Expression::JumpStackDepth),
+            "cycle_count" => return Ok(Expression::CycleCount),
+            "ram" => {
+                self.expect("[")?;
+                let address = self.parse_sum()?;
+                self.expect("]")?;
+                return Ok(Expression::Ram(Box::new(address)));
+            }
+            _ => (),
+        }
+
+        let maybe_stack_index = identifier
+            .strip_prefix("st")
+            .and_then(|index| index.parse::<usize>().ok());
+        if let Some(index) = maybe_stack_index {
+            if index >= NUM_OP_STACK_REGISTERS {
+                bail!("only st0 through st15 are accessible, not “{identifier}”");
+            }
+            return Ok(Expression::StackElement(index));
+        }
+
+        if !self.next_is("[") {
+            return Ok(Expression::Variable {
+                name: identifier,
+                index: None,
+            });
+        }
+        let index = match self.next() {
+            Some(Token::Number(index)) => index.parse()?,
+            _ => bail!("index of variable “{identifier}” must be a number"),
+        };
+        self.expect("]")?;
+        Ok(Expression::Variable {
+            name: identifier,
+            index: Some(index),
+        })
+    }
+}
+
+impl Expression {
+    fn binary(lhs: Self, operator: BinaryOperator, rhs: Self) -> Self {
+        Self::Binary(Box::new(lhs), operator, Box::new(rhs))
+    }
+
+    fn type_of(&self) -> Result<Type> {
+        let expect = |expression: &Self, expected: Type| {
+            let actual = expression.type_of()?;
+            if actual != expected {
+                bail!("expected {expected} but found {actual}");
+            }
+            Ok(())
+        };
+
+        match self {
+            Self::Constant(_)
+            | Self::StackElement(_)
+            | Self::JumpStackDepth
+            | Self::CycleCount
+            | Self::Variable { .. } => Ok(Type::Field),
+            Self::Ram(address) | Self::Negate(address) => {
+                expect(address, Type::Field)?;
+                Ok(Type::Field)
+            }
+            Self::Not(expression) => {
+                expect(expression, Type::Bool)?;
+                Ok(Type::Bool)
+            }
+            Self::Binary(lhs, operator, rhs) => {
+                let (operand_type, result_type) = operator.signature();
+                expect(lhs, operand_type)?;
+                expect(rhs, operand_type)?;
+                Ok(result_type)
+            }
+        }
+    }
+
+    fn evaluate(&self, state: &TritonVMState) -> Result<Value> {
+        let value = match self {
+            Self::Constant(constant) => Value::Field(*constant),
+            Self::StackElement(index) => {
+                let stack = &state.vm_state.op_stack.stack;
+                Value::Field(stack[stack.len() - 1 - index])
+            }
+            Self::Ram(address) => {
+                let address = address.evaluate_field(state)?;
+                let value = state.vm_state.ram.get(&address).copied();
+                Value::Field(value.unwrap_or_default())
+            }
+            Self::JumpStackDepth => Value::Field(state.vm_state.jump_stack.len().into()),
+            Self::CycleCount => Value::Field(state.vm_state.cycle_count.into()),
+            Self::Variable { name, index } => Value::Field(Self::variable(state, name, *index)?),
+            Self::Negate(expression) => Value::Field(-expression.evaluate_field(state)?),
+            Self::Not(expression) => Value::Bool(!expression.evaluate_bool(state)?),
+            Self::Binary(lhs, operator, rhs) => operator.apply(lhs, rhs, state)?,
+        };
+        Ok(value)
+    }
+
+    fn evaluate_field(&self, state: &TritonVMState) -> Result<BFieldElement> {
+        match self.evaluate(state)? {
+            Value::Field(value) => Ok(value),
+            Value::Bool(_) => unreachable!("types are checked when parsing"),
+        }
+    }
+
+    fn evaluate_bool(&self, state: &TritonVMState) -> Result<bool> {
+        match self.evaluate(state)? {
+            Value::Bool(value) => Ok(value),
+            Value::Field(_) => unreachable!("types are checked when parsing"),
+        }
+    }
+
+    /// The topmost stack element annotated with a type hint for the given variable.
+    fn variable(state: &TritonVMState, name: &str, index: Option<usize>) -> Result<BFieldElement> {
+        let hints = &state.type_hints.stack;
+        let stack = &state.vm_state.op_stack.stack;
+        let position = hints.iter().rposition(|hint| {
+            hint.as_ref().is_some_and(|hint| {
+                hint.variable_name == name && hint.index.unwrap_or(0) == index.unwrap_or(0)
+            })
+        });
+        let Some(position) = position else {
+            bail!("no variable “{name}” on the stack");
+        };
+        stack
+            .get(position)
+            .copied()
+            .ok_or_else(|| anyhow!("type hints for variable “{name}” are out of sync"))
+    }
+}
+
+impl BinaryOperator {
+    /// The type of the operands and the type of the result.
+    fn signature(self) -> (Type, Type) {
+        match self {
+            Self::Add | Self::Sub | Self::Mul => (Type::Field, Type::Field),
+            Self::Eq | Self::Ne | Self::Lt | Self::Le | Self::Gt | Self::Ge => {
+                (Type::Field, Type::Bool)
+            }
+            Self::And | Self::Or => (Type::Bool, Type::Bool),
+        }
+    }
+
+    fn apply(self, lhs: &Expression, rhs: &Expression, state: &TritonVMState) -> Result<Value> {
+        if self == Self::And {
+            let holds = lhs.evaluate_bool(state)? && rhs.evaluate_bool(state)?;
+            return Ok(Value::Bool(holds));
+        }
+        if self == Self::Or {
+            let holds = lhs.evaluate_bool(state)? || rhs.evaluate_bool(state)?;
+            return Ok(Value::Bool(holds));
+        }
+
+        let lhs = lhs.evaluate_field(state)?;
+        let rhs = rhs.evaluate_field(state)?;
+        let value = match self {
+            Self::Add => Value::Field(lhs + rhs),
+            Self::Sub => Value::Field(lhs - rhs),
+            Self::Mul => Value::Field(lhs * rhs),
+            Self::Eq => Value::Bool(lhs == rhs),
+            Self::Ne => Value::Bool(lhs != rhs),
+            Self::Lt => Value::Bool(lhs.value() < rhs.value()),
+            Self::Le => Value::Bool(lhs.value() <= rhs.value()),
+            Self::Gt => Value::Bool(lhs.value() > rhs.value()),
+            Self::Ge => Value::Bool(lhs.value() >= rhs.value()),
+            Self::And | Self::Or => unreachable!("handled above"),
+        };
+        Ok(value)
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Field => write!(f, "a number"),
+            Type::Bool => write!(f, "a comparison"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+
+    use crate::action::Action;
+    use crate::action::Execute;
+    use crate::args::TuiArgs;
+    use crate::components::Component;
+
+    use super::*;
+
+    fn holds(condition: &str, state: &TritonVMState) -> bool {
+        Condition::parse(condition)
+            .unwrap()
+            .evaluate(state)
+            .unwrap()
+    }
+
+    #[test]
+    fn compare_stack_elements() {
+        let vm_state = VMState::new(triton_program!(halt), [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        state.vm_state.op_stack.push(bfe!(1001));
+        state.vm_state.op_stack.push(bfe!(3));
+
+        assert!(holds("st0 == 3", &state));
+        assert!(holds("st1 > 1000", &state));
+        assert!(holds("st0 * 2 + 1 == 7 && !(st1 < 1000)", &state));
+        assert!(holds("st0 == 4 || st1 - 1 == 1000", &state));
+        assert!(!holds("st0 != 3", &state));
+    }
+
+    #[test]
+    fn negative_numbers_are_field_elements() {
+        let vm_state = VMState::new(triton_program!(halt), [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        state.vm_state.op_stack.push(bfe!(-1));
+        assert!(holds("st0 == -1", &state));
+        assert!(holds("st0 == 18446744069414584320", &state));
+    }
+
+    #[test]
+    fn read_ram_cycle_count_and_jump_stack_depth() {
+        let vm_state = VMState::new(triton_program!(halt), [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        state.vm_state.ram.insert(bfe!(42), bfe!(17));
+        state.vm_state.cycle_count = 5;
+
+        assert!(holds("ram[40 + 2] == 17", &state));
+        assert!(holds("ram[43] == 0", &state));
+        assert!(holds("cycle_count >= 5 && jump_stack_depth == 0", &state));
+    }
+
+    #[test]
+    fn read_variables_named_by_type_hints() {
+        let mut state = TritonVMState::new(&TuiArgs::default()).unwrap();
+        assert!(holds("program_digest[4] == st15", &state));
+        assert!(holds("program_digest == st11", &state));
+
+        let_assert!(Ok(condition) = Condition::parse("loop_counter == 3"));
+        assert!(let Err(_) = condition.evaluate(&state));
+
+        state.update(Action::Execute(Execute::Step(1))).unwrap();
+        assert!(condition.evaluate(&state).unwrap());
+    }
+
+    #[test]
+    fn malformed_conditions_are_rejected() {
+        assert!(let Err(_) = Condition::parse(""));
+        assert!(let Err(_) = Condition::parse("st0"));
+        assert!(let Err(_) = Condition::parse("st16 == 0"));
+        assert!(let Err(_) = Condition::parse("st0 == 1 == 1"));
+        assert!(let Err(_) = Condition::parse("st0 && st1"));
+        assert!(let Err(_) = Condition::parse("(st0 == 1"));
+        assert!(let Err(_) = Condition::parse("ram[st0 == 1] == 0"));
+        assert!(let Err(_) = Condition::parse("st0 == 1 $"));
+    }
+
+    #[test]
+    fn condition_is_displayed_like_its_source() {
+        let_assert!(Ok(condition) = Condition::parse("  st0 ==  3 "));
+        assert!("st0 ==  3" == condition.to_string());
+    }
+}
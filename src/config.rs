@@ -18,6 +18,7 @@ use tracing::info;
 
 use crate::action::Action;
 use crate::args::*;
+use crate::catchpoint::Catchpoint;
 use crate::mode::Mode;
 
 const DEFAULT_CONFIG: &str = include_str!("../.config/default_config.json");
@@ -29,6 +30,12 @@ pub(crate) struct Config {
 
     #[serde(default)]
     pub styles: Styles,
+
+    /// Catchpoints active right from the start, like `"hash"` or `"call my_function"`. See
+    /// [`Catchpoint`] for the syntax. Only apply to the terminal user interface, not to
+    /// `--headless` runs.
+    #[serde(default)]
+    pub catchpoints: Vec<Catchpoint>,
 }
 
 impl Config {
@@ -260,6 +267,30 @@ fn parse_key_sequence(raw: &str) -> Result<KeyEvents, String> {
     sequences.map(parse_key_event).collect()
 }
 
+/// Split a key sequence like `25s` into its repeat count, if any, and the remaining key events.
+/// Like in vim, a repeat count cannot start with `0`.
+pub(crate) fn split_repeat_count(key_events: &[KeyEvent]) -> (Option<u32>, &[KeyEvent]) {
+    let digit = |key_event: &KeyEvent| match key_event.code {
+        KeyCode::Char(c) if key_event.modifiers == KeyModifiers::NONE => c.to_digit(10),
+        _ => None,
+    };
+    let num_digits = key_events
+        .iter()
+        .map(digit)
+        .take_while(Option::is_some)
+        .count();
+    if key_events.first().and_then(digit).is_none_or(|d| d == 0) {
+        return (None, key_events);
+    }
+
+    let (count, remaining) = key_events.split_at(num_digits);
+    let count = count
+        .iter()
+        .filter_map(digit)
+        .fold(0_u32, |count, d| count.saturating_mul(10).saturating_add(d));
+    (Some(count), remaining)
+}
+
 #[derive(Debug, Default, Clone, Deref, DerefMut)]
 pub(crate) struct Styles(pub HashMap<Mode, HashMap<String, Style>>);
 
@@ -375,6 +406,9 @@ fn parse_color_as_color_index(s: &str) -> u8 {
 #[cfg(test)]
 mod tests {
     use assert2::assert;
+    use assert2::let_assert;
+
+    use crate::action::Execute;
 
     use super::*;
 
@@ -459,6 +493,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn bind_key_to_repeated_execution() {
+        let bindings = r#"{"Home": {"<s>": "Step", "<Shift-s>": "ReverseStep(10)"}}"#;
+        let_assert!(Ok(bindings) = serde_json::from_str::<KeyBindings>(bindings));
+        let home = &bindings[&Mode::Home];
+        let step = Action::Execute(Execute::Step(1));
+        assert!(Some(&step) == home.get(&parse_key_sequence("<s>").unwrap()));
+        let reverse_step = Action::Execute(Execute::ReverseStep(10));
+        assert!(Some(&reverse_step) == home.get(&parse_key_sequence("<Shift-s>").unwrap()));
+
+        let invalid = r#"{"Home": {"<s>": "Step(0)"}}"#;
+        assert!(let Err(_) = serde_json::from_str::<KeyBindings>(invalid));
+    }
+
+    #[test]
+    fn split_repeat_count_from_key_sequence() {
+        let key_sequence = parse_key_sequence("<2><5><s>").unwrap();
+        let (count, remaining) = split_repeat_count(&key_sequence);
+        assert!(Some(25) == count);
+        assert!(parse_key_sequence("<s>").unwrap() == remaining);
+
+        let key_sequence = parse_key_sequence("<t><a>").unwrap();
+        let (count, remaining) = split_repeat_count(&key_sequence);
+        assert!(None == count);
+        assert!(key_sequence == remaining);
+
+        let key_sequence = parse_key_sequence("<0><s>").unwrap();
+        assert!((None, &key_sequence[..]) == split_repeat_count(&key_sequence));
+    }
+
     #[test]
     fn parse_keys_without_modifiers() {
         let empty_modifiers = KeyModifiers::empty();
@@ -523,4 +587,12 @@ mod tests {
         let alt_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT);
         assert!(alt_enter == parse_key_event("AlT-eNtEr").unwrap());
     }
+
+    #[test]
+    fn parse_catchpoints_from_config() {
+        let config = r#"{ "catchpoints": ["hash", "call my_function", "io"] }"#;
+        let config: Config = serde_json::from_str(config).unwrap();
+        assert!(3 == config.catchpoints.len());
+        assert!(Catchpoint::Io == config.catchpoints[2]);
+    }
 }
@@ -0,0 +1,670 @@
+//! Code coverage of Triton assembly, accumulated over one or more runs of a program.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Write;
+use std::path::Path;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
+use fs_err as fs;
+use serde_json::Value;
+use serde_json::json;
+use triton_vm::isa::instruction::AnInstruction;
+use triton_vm::isa::instruction::Instruction;
+use triton_vm::isa::parser::InstructionToken;
+use triton_vm::isa::parser::tokenize;
+use triton_vm::prelude::*;
+
+use crate::args::TuiArgs;
+use crate::components::home::Home;
+use crate::components::home::ProgramLine;
+use crate::triton_vm_state::TritonVMState;
+
+/// Which instructions were executed how often, and which way `skiz` instructions went.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Coverage {
+    /// How often the instruction at each address was executed. Indexed by address.
+    execution_counts: Vec<u32>,
+
+    /// The outcomes of all executed `skiz` instructions, keyed by their address.
+    skiz_outcomes: BTreeMap<usize, SkizOutcomes>,
+
+    num_runs: usize,
+}
+
+/// How often a `skiz` instruction skipped the next instruction, and how often it did not.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct SkizOutcomes {
+    pub skipped: u32,
+    pub not_skipped: u32,
+}
+
+/// [`Coverage`] mapped to the lines of the program's source code.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct CoverageReport {
+    pub source_file: String,
+    pub num_runs: usize,
+    pub lines: Vec<ReportLine>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ReportLine {
+    /// The 1-based line number in the source file.
+    pub source_line: usize,
+    pub item: ReportItem,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum ReportItem {
+    /// A label counts as executed if the instruction following it was executed.
+    Label { label: String, is_executed: bool },
+    Instruction {
+        address: usize,
+        instruction: AnInstruction<String>,
+        execution_count: u32,
+
+        /// Only present for `skiz` instructions.
+        skiz_outcomes: Option<SkizOutcomes>,
+    },
+}
+
+/// Number of covered items out of all items of some kind.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct Ratio {
+    pub covered: usize,
+    pub total: usize,
+}
+
+/// The coverage of a report, by kind of item.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct Summary {
+    pub instructions: Ratio,
+    pub labels: Ratio,
+
+    /// `skiz` instructions that went both ways.
+    pub skiz_branches: Ratio,
+
+    /// `assert` and `assert_vector` instructions that were reached.
+    pub asserts: Ratio,
+}
+
+impl Coverage {
+    pub fn new(program: &Program) -> Self {
+        Self {
+            execution_counts: vec![0; program.len_bwords()],
+            skiz_outcomes: BTreeMap::new(),
+            num_runs: 0,
+        }
+    }
+
+    /// Run the given state to completion, accumulating coverage. An instruction that fails,
+    /// like an `assert` that does not hold, counts as executed. A run that does not halt before
+    /// the given cycle is interrupted, which fails the run like an error. Coverage of
+    /// instructions executed before an error or interrupt is kept.
+    pub fn record_run(&mut self, mut vm_state: VMState, interrupt_cycle: u32) -> Result<()> {
+        self.num_runs += 1;
+        while !vm_state.halting {
+            if vm_state.cycle_count >= interrupt_cycle {
+                bail!("interrupted at cycle {interrupt_cycle}; infinite loop?");
+            }
+            let address = vm_state.instruction_pointer;
+            let instruction = vm_state.current_instruction()?;
+            if let Some(count) = self.execution_counts.get_mut(address) {
+                *count += 1;
+            }
+            if instruction == Instruction::Skiz {
+                let outcomes = self.skiz_outcomes.entry(address).or_default();
+                if vm_state.op_stack[0] == bfe!(0) {
+                    outcomes.skipped += 1;
+                } else {
+                    outcomes.not_skipped += 1;
+                }
+            }
+            vm_state.step()?;
+        }
+        Ok(())
+    }
+
+    fn execution_count(&self, address: usize) -> u32 {
+        self.execution_counts.get(address).copied().unwrap_or(0)
+    }
+
+    /// Map the coverage to the lines of the given source code, which must be the source code of
+    /// the given program.
+    ///
+    /// Labels are taken from the source code since the program only knows about labels that are
+    /// the target of some `call`.
+    pub fn report(
+        &self,
+        program: &Program,
+        source_file: &str,
+        source_code: &str,
+    ) -> Result<CoverageReport> {
+        let mismatch = || anyhow!("source code of “{source_file}” does not match the program");
+        let mut instruction_lines = Home::render_program(program)
+            .into_iter()
+            .filter_map(|line| match line {
+                ProgramLine::Instruction {
+                    address,
+                    instruction,
+                    ..
+                } => Some((address, instruction)),
+                _ => None,
+            })
+            .peekable();
+
+        let mut lines = vec![];
+        for (source_line, label) in Self::source_lines_of_labels_and_instructions(source_code)? {
+            let item = if let Some(label) = label {
+                let next_address = instruction_lines.peek().map(|&(address, _)| address);
+                let is_executed = next_address.is_some_and(|a| self.execution_count(a) > 0);
+                ReportItem::Label { label, is_executed }
+            } else {
+                let (address, instruction) = instruction_lines.next().ok_or_else(mismatch)?;
+                let skiz_outcomes = (instruction == AnInstruction::Skiz).then(|| {
+                    self.skiz_outcomes
+                        .get(&address)
+                        .copied()
+                        .unwrap_or_default()
+                });
+                ReportItem::Instruction {
+                    address,
+                    instruction,
+                    execution_count: self.execution_count(address),
+                    skiz_outcomes,
+                }
+            };
+            lines.push(ReportLine { source_line, item });
+        }
+        if instruction_lines.next().is_some() {
+            return Err(mismatch());
+        }
+
+        Ok(CoverageReport {
+            source_file: source_file.to_string(),
+            num_runs: self.num_runs,
+            lines,
+        })
+    }
+
+    /// The 1-based line numbers of all labels and instructions, in order of appearance. Labels
+    /// come with their name.
+    fn source_lines_of_labels_and_instructions(
+        source_code: &str,
+    ) -> Result<Vec<(usize, Option<String>)>> {
+        let Ok((_, tokens)) = tokenize(source_code) else {
+            bail!("cannot parse source code");
+        };
+        let line_of = |token_str: &str| {
+            let offset = token_str.as_ptr() as usize - source_code.as_ptr() as usize;
+            source_code[..offset].matches('\n').count() + 1
+        };
+        let lines = tokens
+            .into_iter()
+            .filter_map(|token| match token {
+                InstructionToken::Label(label, token_str) => {
+                    Some((line_of(token_str), Some(label)))
+                }
+                InstructionToken::Instruction(_, token_str) => Some((line_of(token_str), None)),
+                _ => None,
+            })
+            .collect();
+        Ok(lines)
+    }
+}
+
+impl SkizOutcomes {
+    pub fn went_both_ways(self) -> bool {
+        self.skipped > 0 && self.not_skipped > 0
+    }
+}
+
+impl Ratio {
+    fn add(&mut self, is_covered: bool) {
+        self.total += 1;
+        self.covered += usize::from(is_covered);
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { covered, total } = *self;
+        let percentage = match total {
+            0 => 100.0,
+            _ => 100.0 * covered as f64 / total as f64,
+        };
+        write!(f, "{covered}/{total} ({percentage:.1}%)")
+    }
+}
+
+impl ReportItem {
+    fn is_assert(&self) -> bool {
+        matches!(
+            self,
+            Self::Instruction {
+                instruction: AnInstruction::Assert | AnInstruction::AssertVector,
+                ..
+            }
+        )
+    }
+}
+
+impl CoverageReport {
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary::default();
+        for line in &self.lines {
+            match &line.item {
+                ReportItem::Label { is_executed, .. } => summary.labels.add(*is_executed),
+                item @ ReportItem::Instruction {
+                    execution_count,
+                    skiz_outcomes,
+                    ..
+                } => {
+                    let is_executed = *execution_count > 0;
+                    summary.instructions.add(is_executed);
+                    if item.is_assert() {
+                        summary.asserts.add(is_executed);
+                    }
+                    if let Some(outcomes) = skiz_outcomes {
+                        summary.skiz_branches.add(outcomes.went_both_ways());
+                    }
+                }
+            }
+        }
+        summary
+    }
+
+    /// Write this report to the given file. Files ending in `.json` get JSON, files ending in
+    /// `.info` or `.lcov` get LCOV, and all others get plain text.
+    pub fn export(&self, path: &Path) -> Result<()> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        let content = match extension {
+            "json" => format!("{:#}", self.json()),
+            "info" | "lcov" => self.lcov(),
+            _ => self.text(),
+        };
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn text(&self) -> String {
+        let summary = self.summary();
+        let mut text = format!(
+            "Coverage of {} over {} run(s)\n\
+            instructions:  {}\n\
+            labels:        {}\n\
+            skiz branches: {} taken both ways\n\
+            asserts:       {} reached\n\n",
+            self.source_file,
+            self.num_runs,
+            summary.instructions,
+            summary.labels,
+            summary.skiz_branches,
+            summary.asserts,
+        );
+
+        for line in &self.lines {
+            let source_line = line.source_line;
+            let _ = match &line.item {
+                ReportItem::Label { label, is_executed } => {
+                    let marker = if *is_executed { " " } else { "!" };
+                    writeln!(text, "{marker} {source_line:>5}  {:>9}  {label}:", "")
+                }
+                ReportItem::Instruction {
+                    instruction,
+                    execution_count,
+                    skiz_outcomes,
+                    ..
+                } => {
+                    let marker = match skiz_outcomes {
+                        _ if *execution_count == 0 => "!",
+                        Some(outcomes) if !outcomes.went_both_ways() => "?",
+                        _ => " ",
+                    };
+                    let skiz_note = skiz_outcomes
+                        .map(|o| {
+                            format!("  (skipped {}×, not skipped {}×)", o.skipped, o.not_skipped)
+                        })
+                        .unwrap_or_default();
+                    writeln!(
+                        text,
+                        "{marker} {source_line:>5}  {execution_count:>9}    {instruction}{skiz_note}"
+                    )
+                }
+            };
+        }
+        text
+    }
+
+    pub fn json(&self) -> Value {
+        let summary = self.summary();
+        let ratio = |r: Ratio| json!({ "covered": r.covered, "total": r.total });
+        let mut instructions = vec![];
+        let mut labels = vec![];
+        let mut skiz = vec![];
+        let mut asserts = vec![];
+        for line in &self.lines {
+            let source_line = line.source_line;
+            match &line.item {
+                ReportItem::Label { label, is_executed } => labels.push(json!({
+                    "line": source_line,
+                    "label": label,
+                    "executed": is_executed,
+                })),
+                item @ ReportItem::Instruction {
+                    address,
+                    instruction,
+                    execution_count,
+                    skiz_outcomes,
+                } => {
+                    instructions.push(json!({
+                        "line": source_line,
+                        "address": address,
+                        "instruction": instruction.to_string(),
+                        "count": execution_count,
+                    }));
+                    if let Some(outcomes) = skiz_outcomes {
+                        skiz.push(json!({
+                            "line": source_line,
+                            "address": address,
+                            "skipped": outcomes.skipped,
+                            "not_skipped": outcomes.not_skipped,
+                        }));
+                    }
+                    if item.is_assert() {
+                        asserts.push(json!({
+                            "line": source_line,
+                            "address": address,
+                            "reached": *execution_count > 0,
+                        }));
+                    }
+                }
+            }
+        }
+
+        json!({
+            "source_file": self.source_file,
+            "runs": self.num_runs,
+            "summary": {
+                "instructions": ratio(summary.instructions),
+                "labels": ratio(summary.labels),
+                "skiz_branches": ratio(summary.skiz_branches),
+                "asserts": ratio(summary.asserts),
+            },
+            "instructions": instructions,
+            "labels": labels,
+            "skiz": skiz,
+            "asserts": asserts,
+        })
+    }
+
+    /// The report in the LCOV tracefile format. Labels are reported as functions, and every
+    /// `skiz` as a branch with the outcomes “skipped” and “not skipped.” A line containing
+    /// multiple instructions counts as often as its most frequently executed instruction.
+    pub fn lcov(&self) -> String {
+        let mut functions = String::new();
+        let mut function_counts = String::new();
+        let mut branches = String::new();
+        let mut line_counts = BTreeMap::<usize, u32>::new();
+        let mut num_functions = Ratio::default();
+        let mut num_branches = Ratio::default();
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let source_line = line.source_line;
+            match &line.item {
+                ReportItem::Label { label, is_executed } => {
+                    let count = self.lines[index..]
+                        .iter()
+                        .find_map(|line| match line.item {
+                            ReportItem::Instruction {
+                                execution_count, ..
+                            } => Some(execution_count),
+                            ReportItem::Label { .. } => None,
+                        })
+                        .unwrap_or(0);
+                    let _ = writeln!(functions, "FN:{source_line},{label}");
+                    let _ = writeln!(function_counts, "FNDA:{count},{label}");
+                    num_functions.add(*is_executed);
+                }
+                ReportItem::Instruction {
+                    execution_count,
+                    skiz_outcomes,
+                    ..
+                } => {
+                    let line_count = line_counts.entry(source_line).or_default();
+                    *line_count = (*line_count).max(*execution_count);
+
+                    let Some(outcomes) = skiz_outcomes else {
+                        continue;
+                    };
+                    let outcomes = [outcomes.skipped, outcomes.not_skipped];
+                    for (branch, taken) in outcomes.into_iter().enumerate() {
+                        let taken = match *execution_count {
+                            0 => "-".to_string(),
+                            _ => taken.to_string(),
+                        };
+                        let _ = writeln!(branches, "BRDA:{source_line},{index},{branch},{taken}");
+                    }
+                    num_branches.add(outcomes[0] > 0);
+                    num_branches.add(outcomes[1] > 0);
+                }
+            }
+        }
+
+        let mut lcov = format!("TN:\nSF:{}\n", self.source_file);
+        lcov.push_str(&functions);
+        lcov.push_str(&function_counts);
+        let _ = writeln!(
+            lcov,
+            "FNF:{}\nFNH:{}",
+            num_functions.total, num_functions.covered
+        );
+        lcov.push_str(&branches);
+        let _ = writeln!(
+            lcov,
+            "BRF:{}\nBRH:{}",
+            num_branches.total, num_branches.covered
+        );
+        for (source_line, count) in &line_counts {
+            let _ = writeln!(lcov, "DA:{source_line},{count}");
+        }
+        let num_hit_lines = line_counts.values().filter(|&&count| count > 0).count();
+        let _ = writeln!(lcov, "LF:{}\nLH:{num_hit_lines}", line_counts.len());
+        lcov.push_str("end_of_record\n");
+        lcov
+    }
+}
+
+/// Run the program given on the command line once for the primary public input and once for
+/// every additional input file, then write the coverage report to the given file.
+///
+/// Returns the summary of the written report.
+pub(crate) fn write_report(args: &TuiArgs, path: &str) -> Result<Summary> {
+    let Some(ref input_args) = args.input_args else {
+        bail!("coverage reports require a program file");
+    };
+    let program = TritonVMState::program_from_args(input_args)?;
+    let mut coverage = Coverage::new(&program);
+
+    let additional_inputs = args.coverage_input.iter().map(|input| Some(input.clone()));
+    for input in [input_args.input.clone()]
+        .into_iter()
+        .chain(additional_inputs)
+    {
+        let mut input_args = input_args.clone();
+        input_args.input = input;
+        let vm_state = TritonVMState::vm_state_with_specified_input(&input_args, program.clone())?;
+        if let Err(err) = coverage.record_run(vm_state, args.interrupt_cycle) {
+            let input = input_args.input.as_deref().unwrap_or("no input");
+            eprintln!("run with input “{input}” failed: {err}");
+        }
+    }
+
+    let source_code = fs::read_to_string(&input_args.program)?;
+    let report = coverage
+        .report(&program, &input_args.program, &source_code)
+        .map_err(|err| anyhow!("cannot map coverage to source code: {err}"))?;
+    report.export(Path::new(path))?;
+    Ok(report.summary())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+    use itertools::Itertools;
+
+    use crate::args::DEFAULT_INTERRUPT_CYCLE;
+
+    use super::*;
+
+    const SOURCE_CODE: &str = "
+        // branch on the public input
+        read_io 1
+        call check
+        halt
+
+        check:
+            dup 0 skiz call non_zero
+            assert
+            return
+
+        non_zero: return
+        unused:   push 0 pop 1 return
+    ";
+
+    fn report_for_inputs(inputs: &[u64]) -> CoverageReport {
+        let program = Program::from_code(SOURCE_CODE).unwrap();
+        let mut coverage = Coverage::new(&program);
+        for &input in inputs {
+            let vm_state = VMState::new(program.clone(), bfe_vec![input].into(), [].into());
+            let _ = coverage.record_run(vm_state, DEFAULT_INTERRUPT_CYCLE);
+        }
+        coverage.report(&program, "test.tasm", SOURCE_CODE).unwrap()
+    }
+
+    #[test]
+    fn labels_and_instructions_are_mapped_to_source_lines() {
+        let report = report_for_inputs(&[1]);
+        let source_lines = report.lines.iter().map(|l| l.source_line).collect_vec();
+        let expected = [3, 4, 5, 7, 8, 8, 8, 9, 10, 12, 12, 13, 13, 13, 13];
+        assert!(expected == source_lines[..]);
+    }
+
+    #[test]
+    fn skiz_branches_are_covered_only_if_taken_both_ways() {
+        let report = report_for_inputs(&[1]);
+        let summary = report.summary();
+        assert!(0 == summary.skiz_branches.covered);
+        assert!(1 == summary.skiz_branches.total);
+        assert!(
+            Ratio {
+                covered: 1,
+                total: 1
+            } == summary.asserts
+        );
+        assert!(
+            Ratio {
+                covered: 2,
+                total: 3
+            } == summary.labels
+        );
+
+        let report = report_for_inputs(&[1, 0]);
+        assert!(1 == report.summary().skiz_branches.covered);
+        assert!(2 == report.num_runs);
+    }
+
+    #[test]
+    fn assertions_need_not_be_reached() {
+        let program = triton_program!(push 0 skiz assert halt);
+        let source_code = "push 0 skiz assert halt";
+        let mut coverage = Coverage::new(&program);
+        let vm_state = VMState::new(program.clone(), [].into(), [].into());
+        coverage
+            .record_run(vm_state, DEFAULT_INTERRUPT_CYCLE)
+            .unwrap();
+        let report = coverage.report(&program, "a.tasm", source_code).unwrap();
+        assert!(0 == report.summary().asserts.covered);
+        assert!(
+            Ratio {
+                covered: 3,
+                total: 4
+            } == report.summary().instructions
+        );
+    }
+
+    #[test]
+    fn failing_runs_keep_coverage_up_to_the_failure() {
+        let report = report_for_inputs(&[0]);
+        let_assert!(Some(assert) = report.lines.iter().find(|l| l.item.is_assert()));
+        let_assert!(
+            ReportItem::Instruction {
+                execution_count,
+                ..
+            } = &assert.item
+        );
+        assert!(1 == *execution_count);
+        assert!(
+            Ratio {
+                covered: 1,
+                total: 1
+            } == report.summary().asserts
+        );
+    }
+
+    #[test]
+    fn non_terminating_runs_are_interrupted_but_keep_their_coverage() {
+        let program = triton_program!(call forever halt forever: recurse);
+        let source_code = "call forever halt forever: recurse";
+        let mut coverage = Coverage::new(&program);
+        let vm_state = VMState::new(program.clone(), [].into(), [].into());
+        let_assert!(Err(err) = coverage.record_run(vm_state, 100));
+        assert!(err.to_string().contains("interrupted"));
+
+        let report = coverage.report(&program, "a.tasm", source_code).unwrap();
+        assert!(
+            Ratio {
+                covered: 2,
+                total: 3
+            } == report.summary().instructions
+        );
+    }
+
+    #[test]
+    fn lcov_lists_functions_branches_and_lines() {
+        let lcov = report_for_inputs(&[1, 0]).lcov();
+        assert!(lcov.starts_with("TN:\nSF:test.tasm\n"));
+        assert!(lcov.contains("FN:7,check\n"));
+        assert!(lcov.contains("FNDA:0,unused\n"));
+        assert!(lcov.contains("BRH:2\n"));
+        assert!(lcov.contains("DA:8,2\n"));
+        assert!(lcov.contains("DA:13,0\n"));
+        assert!(lcov.ends_with("end_of_record\n"));
+    }
+
+    #[test]
+    fn json_and_text_reports_contain_summary() {
+        let report = report_for_inputs(&[1]);
+        let json = report.json();
+        assert!(1 == json["summary"]["asserts"]["total"]);
+        assert!(1 == json["skiz"].as_array().unwrap().len());
+
+        let text = report.text();
+        assert!(text.contains("asserts:       1/1 (100.0%) reached"));
+        assert!(text.contains("?     8"));
+    }
+
+    #[test]
+    fn mismatching_source_code_is_rejected() {
+        let program = triton_program!(push 1 halt);
+        let coverage = Coverage::new(&program);
+        assert!(let Err(_) = coverage.report(&program, "b.tasm", "halt"));
+    }
+}
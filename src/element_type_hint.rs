@@ -3,10 +3,12 @@ use std::cmp::Ordering;
 use arbitrary::Arbitrary;
 use itertools::Itertools;
 use ratatui::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
 
 /// A hint about the type of a single stack element. Helps debugging programs written for Triton VM.
 /// **Does not enforce types.**
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Arbitrary)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
 pub(crate) struct ElementTypeHint {
     /// The name of the type. See [`TypeHint`][type_hint] for details.
     ///
@@ -0,0 +1,298 @@
+//! Running a program to completion without a terminal user interface, for example in scripts
+//! or continuous integration.
+
+use color_eyre::eyre::Result;
+use itertools::Itertools;
+use tokio::sync::mpsc;
+use triton_vm::isa::instruction::AssertionContext;
+use triton_vm::prelude::*;
+
+use crate::action::Action;
+use crate::action::Execute;
+use crate::args::TuiArgs;
+use crate::components::Component;
+use crate::components::home::Home;
+use crate::components::home::ProgramLine;
+use crate::script::FailedExpectation;
+use crate::script::Script;
+use crate::triton_vm_state::TritonVMState;
+
+/// How a headless run ended. Determines the exit code of the process.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Outcome {
+    Halted,
+    Failed,
+    Interrupted,
+
+    /// The script ran to its end without any failing `expect`, whether the program halted or
+    /// not.
+    ScriptCompleted,
+    ExpectationFailed,
+}
+
+impl Outcome {
+    /// Exit codes 1 and 2 are taken by general errors, like missing files, and by invalid
+    /// command line arguments, respectively.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Outcome::Halted | Outcome::ScriptCompleted => 0,
+            Outcome::Failed => 3,
+            Outcome::Interrupted => 4,
+            Outcome::ExpectationFailed => 5,
+        }
+    }
+}
+
+/// Load the program and its inputs like the TUI does, then execute until the VM halts, runs
+/// into an error, or reaches the interrupt cycle. Breakpoints in the program are ignored.
+pub(crate) fn execute(args: &TuiArgs) -> Result<TritonVMState> {
+    let mut state = headless_state(args)?;
+    while state.vm_is_running() {
+        state.update(Action::Execute(Execute::Continue(1)))?;
+    }
+    Ok(state)
+}
+
+/// Load the program and its inputs like the TUI does, then run the script instead of running
+/// the program to completion. Returns the final state and the expectations that failed.
+pub(crate) fn execute_script(
+    args: &TuiArgs,
+    script: &Script,
+) -> Result<(TritonVMState, Vec<FailedExpectation>)> {
+    let mut state = headless_state(args)?;
+    let failed_expectations = script.run(&mut state)?;
+    Ok((state, failed_expectations))
+}
+
+fn headless_state(args: &TuiArgs) -> Result<TritonVMState> {
+    let mut state = TritonVMState::new(args)?;
+
+    // Nobody listens to the executed instructions, but the state expects someone to.
+    let (action_tx, _) = mpsc::unbounded_channel();
+    state.register_action_handler(action_tx)?;
+    Ok(state)
+}
+
+pub(crate) fn outcome(state: &TritonVMState) -> Outcome {
+    if state.error.is_some() {
+        Outcome::Failed
+    } else if state.vm_state.halting {
+        Outcome::Halted
+    } else {
+        Outcome::Interrupted
+    }
+}
+
+pub(crate) fn script_outcome(
+    state: &TritonVMState,
+    failed_expectations: &[FailedExpectation],
+) -> Outcome {
+    if !failed_expectations.is_empty() {
+        Outcome::ExpectationFailed
+    } else if state.error.is_some() {
+        Outcome::Failed
+    } else {
+        Outcome::ScriptCompleted
+    }
+}
+
+/// Public output, cycle count, and the final op stack, top first.
+pub(crate) fn summary(state: &TritonVMState) -> String {
+    let vm_state = &state.vm_state;
+    let output = vm_state.public_output.iter().map(|e| e.value()).join(", ");
+    let stack = vm_state.op_stack.stack.iter().rev();
+    let stack = stack.map(|e| e.value()).join(", ");
+    format!(
+        "output: [{output}]\n\
+         cycles: {}\n\
+         stack:  [{stack}]",
+        vm_state.cycle_count
+    )
+}
+
+/// Why execution stopped early, if it did. For a failing instruction, includes the source of
+/// that instruction and any assertion context like its `error_id`.
+pub(crate) fn failure_report(state: &TritonVMState) -> Option<String> {
+    let vm_state = &state.vm_state;
+    let cycle = vm_state.cycle_count;
+    let ip = vm_state.instruction_pointer;
+    match outcome(state) {
+        Outcome::Halted => None,
+        Outcome::ScriptCompleted | Outcome::ExpectationFailed => {
+            unreachable!("only `script_outcome` reports the outcome of scripts")
+        }
+        Outcome::Interrupted => Some(format!(
+            "interrupted at cycle {cycle}, ip {ip}: \
+             increase `--interrupt-cycle` to run longer"
+        )),
+        Outcome::Failed => {
+            let error = state.error.as_ref()?;
+            let instruction = match failing_instruction(&vm_state.program, ip) {
+                Some(instruction) => format!(" `{instruction}`"),
+                None => String::new(),
+            };
+            Some(format!(
+                "error at cycle {cycle}, ip {ip}{instruction}: {error}"
+            ))
+        }
+    }
+}
+
+/// The instruction at the given address, including its assertion context.
+fn failing_instruction(program: &Program, address: usize) -> Option<String> {
+    let program_lines = Home::render_program(program);
+    let mut lines = program_lines.iter().skip_while(
+        |line| !matches!(line, ProgramLine::Instruction { address: a, .. } if *a == address),
+    );
+    let Some(ProgramLine::Instruction { instruction, .. }) = lines.next() else {
+        return None;
+    };
+    let mut instruction = instruction.to_string();
+    if let Some(ProgramLine::AssertionContext(AssertionContext::ID(id))) = lines.next() {
+        instruction = format!("{instruction} error_id {id}");
+    }
+    Some(instruction)
+}
+
+/// Execute, or run the script if there is one, print the result, and return the exit code of
+/// the process.
+pub(crate) fn run(args: &TuiArgs) -> Result<i32> {
+    let (state, outcome) = match args.script {
+        Some(ref path) => run_script(args, path)?,
+        None => {
+            let state = execute(args)?;
+            println!("{}", summary(&state));
+            if let Some(report) = failure_report(&state) {
+                eprintln!("{report}");
+            }
+            let outcome = outcome(&state);
+            (state, outcome)
+        }
+    };
+    if let Some(ref path) = args.profile {
+        state.export_profile(path)?;
+    }
+    Ok(outcome.exit_code())
+}
+
+fn run_script(args: &TuiArgs, path: &str) -> Result<(TritonVMState, Outcome)> {
+    let script = Script::load(path)?;
+    let (state, failed_expectations) = execute_script(args, &script)?;
+    println!("{}", summary(&state));
+    for failed_expectation in &failed_expectations {
+        eprintln!("{failed_expectation}");
+    }
+    if state.error.is_some()
+        && let Some(report) = failure_report(&state)
+    {
+        eprintln!("{report}");
+    }
+    let outcome = script_outcome(&state, &failed_expectations);
+    Ok((state, outcome))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use assert2::assert;
+    use assert2::let_assert;
+    use fs_err as fs;
+
+    use crate::args::InputArgs;
+    use crate::args_tests::args_for_test_program_with_test_input;
+
+    use super::*;
+
+    fn args_for_program(file_name: &str, code: &str) -> TuiArgs {
+        let path = temp_dir().join(file_name);
+        fs::write(&path, code).unwrap();
+        let input_args = InputArgs {
+            program: path.display().to_string(),
+            input: None,
+            non_determinism: None,
+        };
+        TuiArgs {
+            input_args: Some(input_args),
+            ..TuiArgs::default()
+        }
+    }
+
+    #[test]
+    fn example_program_runs_into_its_deliberately_failing_assertion() {
+        let args = args_for_test_program_with_test_input();
+        let_assert!(Ok(state) = execute(&args));
+        assert!(Outcome::Failed == outcome(&state));
+        let_assert!(Some(report) = failure_report(&state));
+        assert!(report.contains("error_id -17"));
+    }
+
+    #[test]
+    fn halting_program_has_no_failure_report() {
+        let args = args_for_program("triton_tui_headless_halt.tasm", "break push 1 halt");
+        let_assert!(Ok(state) = execute(&args));
+        assert!(Outcome::Halted == outcome(&state));
+        assert!(failure_report(&state).is_none());
+        assert!(0 == outcome(&state).exit_code());
+    }
+
+    #[test]
+    fn summary_lists_output_and_stack_top_first() {
+        let code = "push 1 write_io 1 push 2 push 3 write_io 2 push 4 push 5 halt";
+        let args = args_for_program("triton_tui_headless_summary.tasm", code);
+        let_assert!(Ok(state) = execute(&args));
+        let summary = summary(&state);
+        assert!(summary.contains("output: [1, 3, 2]"));
+        assert!(summary.contains("cycles: 8"));
+        assert!(summary.contains("stack:  [5, 4, 0,"));
+    }
+
+    #[test]
+    fn failing_assertion_is_reported_with_context() {
+        let code = "push 1 push 0 assert error_id 42 halt";
+        let args = args_for_program("triton_tui_headless_failure.tasm", code);
+        let_assert!(Ok(state) = execute(&args));
+        assert!(Outcome::Failed == outcome(&state));
+        let_assert!(Some(report) = failure_report(&state));
+        assert!(report.contains("cycle 2, ip 4 `assert error_id 42`"));
+        assert!(report.contains("[42] expected 1, got 0"));
+    }
+
+    #[test]
+    fn infinite_loop_is_interrupted() {
+        let code = "loop: call loop";
+        let mut args = args_for_program("triton_tui_headless_loop.tasm", code);
+        args.interrupt_cycle = 100;
+        let_assert!(Ok(state) = execute(&args));
+        assert!(Outcome::Interrupted == outcome(&state));
+        assert!(100 == state.vm_state.cycle_count);
+        assert!(4 == outcome(&state).exit_code());
+    }
+
+    #[test]
+    fn script_runs_instead_of_running_to_completion() {
+        let code = "push 41 push 1 add push 0 assert halt";
+        let args = args_for_program("triton_tui_headless_script.tasm", code);
+        let source = "step 3\nexpect st0 == 42\nexpect st0 == 0\n";
+        let_assert!(Ok(script) = Script::parse(source));
+
+        let_assert!(Ok((state, failed_expectations)) = execute_script(&args, &script));
+        assert!(3 == state.vm_state.cycle_count);
+        let outcome = script_outcome(&state, &failed_expectations);
+        assert!(Outcome::ExpectationFailed == outcome);
+        let_assert!([failed_expectation] = failed_expectations.as_slice());
+        let message = failed_expectation.to_string();
+        assert!(message.starts_with("line 3: expected “st0 == 0” at cycle 3, ip 5"));
+    }
+
+    #[test]
+    fn script_that_runs_to_its_end_succeeds_without_halting() {
+        let code = "push 1 push 2 halt";
+        let args = args_for_program("triton_tui_headless_script_end.tasm", code);
+        let_assert!(Ok(script) = Script::parse("step\nexpect st0 == 1"));
+        let_assert!(Ok((state, failed_expectations)) = execute_script(&args, &script));
+        let outcome = script_outcome(&state, &failed_expectations);
+        assert!(Outcome::ScriptCompleted == outcome);
+        assert!(0 == outcome.exit_code());
+    }
+}
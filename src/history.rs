@@ -0,0 +1,378 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::mem::size_of;
+
+use triton_vm::prelude::*;
+
+use crate::element_type_hint::ElementTypeHint;
+use crate::profile::Profile;
+use crate::shadow_memory::ShadowMemory;
+use crate::table_heights::TableHeights;
+
+/// Snapshots of past states of Triton VM. Since execution is deterministic, any earlier cycle
+/// can be reached by restoring the most recent preceding [`Checkpoint`] and re-executing from
+/// there.
+///
+/// The memory taken up by checkpoints is bounded. Once the limit is exceeded, the interval
+/// between checkpoints is doubled and every other checkpoint is dropped. This keeps all cycles
+/// reachable at the cost of re-executing more instructions. The initial checkpoint is never
+/// dropped, and neither are checkpoints of states the user edited, since re-execution cannot
+/// reproduce those.
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+    checkpoints: BTreeMap<u32, Checkpoint>,
+
+    /// The cycles of checkpoints recorded with [`Self::record_edit`].
+    edited_cycles: BTreeSet<u32>,
+
+    /// The number of cycles between two consecutive checkpoints. Bounds the number of
+    /// instructions that have to be re-executed to reach any earlier cycle.
+    interval: u32,
+
+    /// The estimated number of bytes taken up by all checkpoints.
+    memory_usage: usize,
+
+    /// The maximum number of bytes checkpoints may take up.
+    memory_limit: usize,
+}
+
+/// Everything required to continue execution from some cycle onwards.
+///
+/// Since the program never changes during a session, the [`VMState`] of a checkpoint does not
+/// hold on to it. Restoring a checkpoint requires putting the program back in place.
+#[derive(Debug, Clone)]
+pub(crate) struct Checkpoint {
+    pub vm_state: VMState,
+    pub type_hints: ShadowMemory,
+    pub profile: Profile,
+    pub table_heights: TableHeights,
+
+    /// The RAM address most recently accessed by any instruction.
+    pub most_recent_ram_address: BFieldElement,
+}
+
+/// A [`Checkpoint`] the user gave a name, like “after setup”, to return to it at will.
+#[derive(Debug, Clone)]
+pub(crate) struct NamedCheckpoint {
+    pub name: String,
+    pub checkpoint: Checkpoint,
+}
+
+impl History {
+    /// The number of cycles between two consecutive checkpoints as long as the memory limit is
+    /// not exceeded.
+    pub const INITIAL_CHECKPOINT_INTERVAL: u32 = 1_000;
+
+    pub fn new(initial_checkpoint: Checkpoint, memory_limit: usize) -> Self {
+        let cycle = initial_checkpoint.cycle();
+        let memory_usage = initial_checkpoint.estimated_size();
+        let checkpoints = BTreeMap::from([(cycle, initial_checkpoint)]);
+        Self {
+            checkpoints,
+            edited_cycles: BTreeSet::new(),
+            interval: Self::INITIAL_CHECKPOINT_INTERVAL,
+            memory_usage,
+            memory_limit,
+        }
+    }
+
+    pub fn num_checkpoints(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// The estimated number of bytes taken up by all checkpoints.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
+    pub fn memory_limit(&self) -> usize {
+        self.memory_limit
+    }
+
+    /// The checkpoint the history started out with.
+    pub fn initial_checkpoint(&self) -> &Checkpoint {
+        let (_, checkpoint) = self.checkpoints.first_key_value().unwrap();
+        checkpoint
+    }
+
+    /// The earliest cycle that can be travelled to.
+    pub fn earliest_cycle(&self) -> u32 {
+        let (&cycle, _) = self.checkpoints.first_key_value().unwrap();
+        cycle
+    }
+
+    pub fn checkpoint_is_due(&self, cycle: u32) -> bool {
+        cycle.is_multiple_of(self.interval) && !self.checkpoints.contains_key(&cycle)
+    }
+
+    pub fn record(&mut self, checkpoint: Checkpoint) {
+        self.memory_usage += checkpoint.estimated_size();
+        if let Some(replaced) = self.checkpoints.insert(checkpoint.cycle(), checkpoint) {
+            self.memory_usage -= replaced.estimated_size();
+        }
+        while self.memory_usage > self.memory_limit && self.can_thin_out() {
+            self.thin_out();
+        }
+    }
+
+    /// Record a checkpoint of a state the user edited, replacing any checkpoint of the same
+    /// cycle. Such a checkpoint is never dropped to save memory.
+    pub fn record_edit(&mut self, checkpoint: Checkpoint) {
+        self.edited_cycles.insert(checkpoint.cycle());
+        self.record(checkpoint);
+    }
+
+    /// The checkpoint recorded with [`Self::record_edit`] at exactly the given cycle, if any.
+    pub fn edited_checkpoint_at(&self, cycle: u32) -> Option<&Checkpoint> {
+        if !self.edited_cycles.contains(&cycle) {
+            return None;
+        }
+        self.checkpoints.get(&cycle)
+    }
+
+    /// Whether the user edited any state after the initial one. Re-executing from the initial
+    /// checkpoint does not reproduce such a run.
+    pub fn has_edits_after_start(&self) -> bool {
+        let earliest_cycle = self.earliest_cycle();
+        self.edited_cycles
+            .iter()
+            .any(|&cycle| cycle != earliest_cycle)
+    }
+
+    /// Drop all checkpoints after the given cycle, for example because execution will continue
+    /// differently from there on.
+    pub fn forget_after(&mut self, cycle: u32) {
+        let forgotten = self.checkpoints.split_off(&(cycle + 1));
+        self.edited_cycles.split_off(&(cycle + 1));
+        self.memory_usage -= forgotten
+            .values()
+            .map(Checkpoint::estimated_size)
+            .sum::<usize>();
+    }
+
+    /// Append input to that of every checkpoint, as if it had been part of the input from the
+    /// start.
+    pub fn append_input(&mut self, public_input: &[BFieldElement], secret_input: &[BFieldElement]) {
+        for checkpoint in self.checkpoints.values_mut() {
+            checkpoint.append_input(public_input, secret_input);
+        }
+        let num_elements = public_input.len() + secret_input.len();
+        self.memory_usage += self.checkpoints.len() * num_elements * size_of::<BFieldElement>();
+    }
+
+    /// Whether there is any checkpoint [`Self::thin_out`] might drop.
+    fn can_thin_out(&self) -> bool {
+        let earliest_cycle = self.earliest_cycle();
+        self.checkpoints
+            .keys()
+            .any(|&cycle| cycle != earliest_cycle && !self.edited_cycles.contains(&cycle))
+    }
+
+    /// Double the checkpoint interval and drop all checkpoints not fitting the new interval.
+    fn thin_out(&mut self) {
+        self.interval = self.interval.saturating_mul(2);
+        let earliest_cycle = self.earliest_cycle();
+        let interval = self.interval;
+        let edited_cycles = &self.edited_cycles;
+        let mut memory_usage = self.memory_usage;
+        self.checkpoints.retain(|&cycle, checkpoint| {
+            let keep = cycle == earliest_cycle
+                || edited_cycles.contains(&cycle)
+                || cycle.is_multiple_of(interval);
+            if !keep {
+                memory_usage -= checkpoint.estimated_size();
+            }
+            keep
+        });
+        self.memory_usage = memory_usage;
+    }
+
+    /// The most recent checkpoint at or before the given cycle.
+    pub fn checkpoint_at_or_before(&self, cycle: u32) -> Option<&Checkpoint> {
+        let (_, checkpoint) = self.checkpoints.range(..=cycle).next_back()?;
+        Some(checkpoint)
+    }
+
+    /// The cycles of all checkpoints strictly before the given cycle, most recent first.
+    pub fn checkpoint_cycles_before(&self, cycle: u32) -> Vec<u32> {
+        self.checkpoints
+            .range(..cycle)
+            .rev()
+            .map(|(&c, _)| c)
+            .collect()
+    }
+}
+
+impl Checkpoint {
+    pub fn new(
+        mut vm_state: VMState,
+        type_hints: ShadowMemory,
+        profile: Profile,
+        table_heights: TableHeights,
+        most_recent_ram_address: BFieldElement,
+    ) -> Self {
+        vm_state.program = Program::new(&[]);
+        Self {
+            vm_state,
+            type_hints,
+            profile,
+            table_heights,
+            most_recent_ram_address,
+        }
+    }
+
+    pub fn cycle(&self) -> u32 {
+        self.vm_state.cycle_count
+    }
+
+    /// Append input to the input not yet read as of this checkpoint.
+    pub fn append_input(&mut self, public_input: &[BFieldElement], secret_input: &[BFieldElement]) {
+        self.vm_state.public_input.extend(public_input);
+        self.vm_state.secret_individual_tokens.extend(secret_input);
+    }
+
+    /// A rough estimate of the number of bytes this checkpoint takes up, ignoring the overhead
+    /// of the underlying collections.
+    pub fn estimated_size(&self) -> usize {
+        let vm_state = &self.vm_state;
+        let num_elements = 2 * vm_state.ram.len()
+            + vm_state.op_stack.stack.len()
+            + 2 * vm_state.jump_stack.len()
+            + vm_state.public_input.len()
+            + vm_state.public_output.len()
+            + vm_state.secret_individual_tokens.len()
+            + Digest::LEN * vm_state.secret_digests.len();
+        let num_type_hints = self.type_hints.stack.len() + self.type_hints.ram.len();
+        let type_hint_size = size_of::<BFieldElement>() + size_of::<Option<ElementTypeHint>>();
+
+        size_of::<Self>()
+            + num_elements * size_of::<BFieldElement>()
+            + num_type_hints * type_hint_size
+            + self.profile.estimated_size()
+            + self.table_heights.estimated_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+
+    use super::*;
+
+    fn checkpoint_at_cycle(cycle: u32) -> Checkpoint {
+        let program = triton_program!(halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        vm_state.cycle_count = cycle;
+        Checkpoint {
+            profile: Profile::new(&vm_state),
+            table_heights: TableHeights::new(&vm_state.program),
+            vm_state,
+            type_hints: ShadowMemory::new_for_default_initial_state(),
+            most_recent_ram_address: bfe!(0),
+        }
+    }
+
+    #[test]
+    fn find_checkpoints_preceding_a_cycle() {
+        let interval = History::INITIAL_CHECKPOINT_INTERVAL;
+        let mut history = History::new(checkpoint_at_cycle(7), usize::MAX);
+        history.record(checkpoint_at_cycle(interval));
+        history.record(checkpoint_at_cycle(2 * interval));
+        assert!(7 == history.earliest_cycle());
+
+        assert!(history.checkpoint_at_or_before(6).is_none());
+        let_assert!(Some(checkpoint) = history.checkpoint_at_or_before(interval + 1));
+        assert!(interval == checkpoint.cycle());
+        let_assert!(Some(checkpoint) = history.checkpoint_at_or_before(interval));
+        assert!(interval == checkpoint.cycle());
+
+        assert!(vec![interval, 7] == history.checkpoint_cycles_before(2 * interval));
+    }
+
+    #[test]
+    fn checkpoints_are_due_periodically_and_only_once() {
+        let interval = History::INITIAL_CHECKPOINT_INTERVAL;
+        let mut history = History::new(checkpoint_at_cycle(0), usize::MAX);
+        assert!(!history.checkpoint_is_due(0));
+        assert!(!history.checkpoint_is_due(interval - 1));
+        assert!(history.checkpoint_is_due(interval));
+
+        history.record(checkpoint_at_cycle(interval));
+        assert!(!history.checkpoint_is_due(interval));
+    }
+
+    #[test]
+    fn exceeding_memory_limit_thins_out_checkpoints() {
+        let interval = History::INITIAL_CHECKPOINT_INTERVAL;
+        let checkpoint_size = checkpoint_at_cycle(0).estimated_size();
+        let mut history = History::new(checkpoint_at_cycle(0), 4 * checkpoint_size);
+        for i in 1..=4 {
+            history.record(checkpoint_at_cycle(i * interval));
+        }
+        assert!(3 == history.num_checkpoints());
+        assert!(history.memory_usage() <= history.memory_limit());
+
+        assert!(0 == history.earliest_cycle());
+        assert!(vec![2 * interval, 0] == history.checkpoint_cycles_before(4 * interval));
+        assert!(!history.checkpoint_is_due(5 * interval));
+        assert!(history.checkpoint_is_due(6 * interval));
+    }
+
+    #[test]
+    fn forgetting_checkpoints_after_a_cycle_frees_their_memory() {
+        let interval = History::INITIAL_CHECKPOINT_INTERVAL;
+        let mut history = History::new(checkpoint_at_cycle(0), usize::MAX);
+        let memory_usage_of_initial_checkpoint = history.memory_usage();
+        history.record(checkpoint_at_cycle(interval));
+        history.record(checkpoint_at_cycle(2 * interval));
+
+        history.forget_after(interval);
+        assert!(2 == history.num_checkpoints());
+        history.forget_after(interval - 1);
+        assert!(1 == history.num_checkpoints());
+        assert!(memory_usage_of_initial_checkpoint == history.memory_usage());
+        assert!(history.checkpoint_is_due(interval));
+    }
+
+    #[test]
+    fn appended_input_is_available_from_every_checkpoint() {
+        let interval = History::INITIAL_CHECKPOINT_INTERVAL;
+        let mut history = History::new(checkpoint_at_cycle(0), usize::MAX);
+        history.record(checkpoint_at_cycle(interval));
+        history.append_input(&bfe_array![1, 2], &bfe_array![3]);
+
+        for cycle in [0, interval] {
+            let checkpoint = history.checkpoint_at_or_before(cycle).unwrap();
+            let vm_state = &checkpoint.vm_state;
+            assert!(bfe_vec![1, 2] == Vec::from(vm_state.public_input.clone()));
+            assert!(bfe_vec![3] == Vec::from(vm_state.secret_individual_tokens.clone()));
+        }
+        let checkpoint_sizes = [0, interval]
+            .map(|cycle| history.checkpoint_at_or_before(cycle).unwrap())
+            .map(Checkpoint::estimated_size);
+        assert!(checkpoint_sizes.iter().sum::<usize>() == history.memory_usage());
+    }
+
+    #[test]
+    fn checkpoints_of_edited_states_are_kept_even_if_they_exceed_memory_limit() {
+        let mut history = History::new(checkpoint_at_cycle(0), 0);
+        history.record_edit(checkpoint_at_cycle(7));
+        history.record(checkpoint_at_cycle(History::INITIAL_CHECKPOINT_INTERVAL));
+        assert!(2 == history.num_checkpoints());
+        assert!(history.edited_checkpoint_at(7).is_some());
+        assert!(history.edited_checkpoint_at(0).is_none());
+
+        history.forget_after(6);
+        assert!(history.edited_checkpoint_at(7).is_none());
+        assert!(1 == history.num_checkpoints());
+    }
+
+    #[test]
+    fn initial_checkpoint_is_kept_even_if_it_exceeds_memory_limit() {
+        let mut history = History::new(checkpoint_at_cycle(3), 0);
+        history.record(checkpoint_at_cycle(History::INITIAL_CHECKPOINT_INTERVAL));
+        assert!(1 == history.num_checkpoints());
+        assert!(3 == history.earliest_cycle());
+    }
+}
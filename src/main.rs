@@ -20,14 +20,28 @@ pub(crate) mod action;
 pub(crate) mod args;
 #[cfg(test)]
 pub(crate) mod args_tests;
+pub(crate) mod catchpoint;
+pub(crate) mod command;
 pub(crate) mod components;
+pub(crate) mod condition;
 pub(crate) mod config;
+pub(crate) mod coverage;
 pub(crate) mod element_type_hint;
+pub(crate) mod headless;
+pub(crate) mod history;
 pub(crate) mod mode;
+pub(crate) mod profile;
+pub(crate) mod prover;
+pub(crate) mod reload;
+pub(crate) mod script;
+pub(crate) mod session;
 pub(crate) mod shadow_memory;
+pub(crate) mod table_heights;
 pub(crate) mod triton_tui;
 pub(crate) mod triton_vm_state;
 pub(crate) mod tui;
+pub(crate) mod verifier;
+pub(crate) mod watchpoint;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -35,6 +49,19 @@ async fn main() -> Result<()> {
     initialize_panic_handler()?;
 
     let args = TuiArgs::parse();
+    if let Some(ref path) = args.coverage {
+        let summary = coverage::write_report(&args, path)?;
+        println!("instructions:  {}", summary.instructions);
+        println!("labels:        {}", summary.labels);
+        println!("skiz branches: {} taken both ways", summary.skiz_branches);
+        println!("asserts:       {} reached", summary.asserts);
+        return Ok(());
+    }
+    if args.headless {
+        let exit_code = headless::run(&args)?;
+        std::process::exit(exit_code);
+    }
+
     let mut triton_tui = TritonTUI::new(args)?;
     let execution_result = triton_tui.run().await;
     if let Err(ref err) = execution_result {
@@ -11,6 +11,10 @@ pub(crate) enum Mode {
     Home,
     Memory,
     Help,
+    Profiler,
+    Checkpoints,
+    Verifier,
+    InputEditor,
 }
 
 impl Mode {
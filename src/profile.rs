@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::path::Path;
+
+use color_eyre::eyre::Result;
+use fs_err as fs;
+use itertools::Itertools;
+use serde_json::Value;
+use serde_json::json;
+use triton_vm::prelude::*;
+
+/// The number of cycles spent in each function, broken down by call stack, and the number of
+/// times each instruction was executed.
+///
+/// Functions are identified by their entry address, which is the destination of the `call`
+/// instruction that entered them. Every executed instruction is attributed to the function that
+/// is active while executing it. In particular, `call` is attributed to the caller, and `return`
+/// to the callee.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Profile {
+    /// The call tree. The root is at index 0, and every node comes after its parent.
+    nodes: Vec<CallNode>,
+
+    /// The nodes of all currently active call frames, outermost first. Mirrors Triton VM's jump
+    /// stack, with the root in addition.
+    active_nodes: Vec<usize>,
+
+    /// How often the instruction at each address has been executed. Indexed by address.
+    execution_counts: Vec<u32>,
+}
+
+/// A function in the call tree, as called through the path of functions leading to it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct CallNode {
+    /// The entry address of the function.
+    pub address: u64,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    pub num_calls: u32,
+
+    /// The number of cycles spent in this function itself, excluding called functions.
+    pub exclusive_cycles: u32,
+}
+
+/// The cycles spent in all invocations of a function, across all call stacks.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct FunctionSummary {
+    pub label: String,
+    pub num_calls: u32,
+
+    /// The number of cycles spent in the function, including called functions. Recursive calls
+    /// are counted only once.
+    pub inclusive_cycles: u32,
+    pub exclusive_cycles: u32,
+}
+
+impl Profile {
+    pub const ROOT: usize = 0;
+
+    /// A profile in which no cycles have been spent yet. If the given state's jump stack is not
+    /// empty, the corresponding call frames count as entered once.
+    pub fn new(vm_state: &VMState) -> Self {
+        let root = CallNode::new(0, None);
+        let mut profile = Self {
+            nodes: vec![root],
+            active_nodes: vec![Self::ROOT],
+            execution_counts: vec![0; vm_state.program.len_bwords()],
+        };
+        profile.mirror_jump_stack(vm_state);
+        profile
+    }
+
+    pub fn nodes(&self) -> &[CallNode] {
+        &self.nodes
+    }
+
+    /// Attribute the most recently executed instruction, found at the given address, to the
+    /// active function. Then, enter or leave functions according to the given state's jump stack.
+    pub fn record_cycle(&mut self, address: usize, vm_state: &VMState) {
+        let &active_node = self.active_nodes.last().unwrap();
+        self.nodes[active_node].exclusive_cycles += 1;
+        if let Some(count) = self.execution_counts.get_mut(address) {
+            *count += 1;
+        }
+        self.mirror_jump_stack(vm_state);
+    }
+
+    /// How often the instruction at the given address has been executed.
+    pub fn execution_count(&self, address: usize) -> u32 {
+        self.execution_counts.get(address).copied().unwrap_or(0)
+    }
+
+    /// The number of executions of the most frequently executed instruction.
+    pub fn max_execution_count(&self) -> u32 {
+        self.execution_counts.iter().copied().max().unwrap_or(0)
+    }
+
+    fn mirror_jump_stack(&mut self, vm_state: &VMState) {
+        let depth = vm_state.jump_stack.len() + 1;
+        self.active_nodes.truncate(depth);
+        while self.active_nodes.len() < depth {
+            let &parent = self.active_nodes.last().unwrap();
+            let (_, destination) = vm_state.jump_stack[self.active_nodes.len() - 1];
+            let child = self.child(parent, destination.value());
+            self.nodes[child].num_calls += 1;
+            self.active_nodes.push(child);
+        }
+    }
+
+    /// The child of the given node for the function at the given address, created if necessary.
+    fn child(&mut self, parent: usize, address: u64) -> usize {
+        let existing_child = self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].address == address);
+        if let Some(child) = existing_child {
+            return child;
+        }
+
+        let child = self.nodes.len();
+        self.nodes.push(CallNode::new(address, Some(parent)));
+        self.nodes[parent].children.push(child);
+        child
+    }
+
+    /// The number of cycles spent in each node, including its descendants. Indexed like
+    /// [`Self::nodes`].
+    pub fn inclusive_cycles(&self) -> Vec<u32> {
+        let mut inclusive_cycles = self.nodes.iter().map(|n| n.exclusive_cycles).collect_vec();
+        for (index, node) in self.nodes.iter().enumerate().rev() {
+            if let Some(parent) = node.parent {
+                inclusive_cycles[parent] += inclusive_cycles[index];
+            }
+        }
+        inclusive_cycles
+    }
+
+    /// The total cycles spent per function, in no particular order.
+    pub fn function_summaries(&self, program: &Program) -> Vec<FunctionSummary> {
+        let inclusive_cycles = self.inclusive_cycles();
+        let mut summaries = HashMap::<u64, FunctionSummary>::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            let summary = summaries
+                .entry(node.address)
+                .or_insert_with(|| FunctionSummary {
+                    label: program.label_for_address(node.address),
+                    num_calls: 0,
+                    inclusive_cycles: 0,
+                    exclusive_cycles: 0,
+                });
+            summary.num_calls += node.num_calls;
+            summary.exclusive_cycles += node.exclusive_cycles;
+            if !self.is_recursive_call(index) {
+                summary.inclusive_cycles += inclusive_cycles[index];
+            }
+        }
+        summaries.into_values().collect()
+    }
+
+    /// Whether any ancestor of the given node belongs to the same function.
+    fn is_recursive_call(&self, node: usize) -> bool {
+        let address = self.nodes[node].address;
+        let mut ancestor = self.nodes[node].parent;
+        while let Some(index) = ancestor {
+            if self.nodes[index].address == address {
+                return true;
+            }
+            ancestor = self.nodes[index].parent;
+        }
+        false
+    }
+
+    /// Write this profile to the given file. Files ending in `.json` use the [speedscope] format,
+    /// all others the folded stacks understood by flamegraph tools.
+    ///
+    /// [speedscope]: https://www.speedscope.app
+    pub fn export(&self, program: &Program, path: &Path) -> Result<()> {
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension == "json");
+        let content = if is_json {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy();
+            self.speedscope(program, &name).to_string()
+        } else {
+            self.folded_stacks(program)
+        };
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// One line per call stack that executed any instructions, like `main;foo;bar 42`. The
+    /// number is the count of cycles spent in the innermost function of that call stack.
+    pub fn folded_stacks(&self, program: &Program) -> String {
+        self.call_stacks_with_cycles()
+            .map(|(stack, cycles)| {
+                let stack = stack
+                    .into_iter()
+                    .map(|node| program.label_for_address(self.nodes[node].address))
+                    .join(";");
+                format!("{stack} {cycles}\n")
+            })
+            .collect()
+    }
+
+    /// The profile in speedscope's “sampled” format, where every call stack is a sample weighed
+    /// by the cycles spent in it.
+    pub fn speedscope(&self, program: &Program, name: &str) -> Value {
+        let mut frames = Vec::<String>::new();
+        let mut frame_index = |label: String| match frames.iter().position(|f| *f == label) {
+            Some(index) => index,
+            None => {
+                frames.push(label);
+                frames.len() - 1
+            }
+        };
+
+        let mut samples = vec![];
+        let mut weights = vec![];
+        for (stack, cycles) in self.call_stacks_with_cycles() {
+            let sample = stack
+                .into_iter()
+                .map(|node| frame_index(program.label_for_address(self.nodes[node].address)))
+                .collect_vec();
+            samples.push(sample);
+            weights.push(cycles);
+        }
+        let total_cycles = self.inclusive_cycles()[Self::ROOT];
+        let frames = frames.into_iter().map(|name| json!({ "name": name }));
+
+        json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "exporter": format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            "name": name,
+            "activeProfileIndex": 0,
+            "shared": { "frames": frames.collect_vec() },
+            "profiles": [{
+                "type": "sampled",
+                "name": name,
+                "unit": "none",
+                "startValue": 0,
+                "endValue": total_cycles,
+                "samples": samples,
+                "weights": weights,
+            }],
+        })
+    }
+
+    /// All call stacks, outermost function first, in which at least one cycle was spent,
+    /// together with the number of cycles spent in the innermost function.
+    fn call_stacks_with_cycles(&self) -> impl Iterator<Item = (Vec<usize>, u32)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.exclusive_cycles > 0)
+            .map(|(index, node)| (self.call_stack(index), node.exclusive_cycles))
+    }
+
+    /// The path from the root to the given node, both inclusive.
+    fn call_stack(&self, node: usize) -> Vec<usize> {
+        let mut stack = vec![node];
+        while let Some(parent) = self.nodes[*stack.last().unwrap()].parent {
+            stack.push(parent);
+        }
+        stack.reverse();
+        stack
+    }
+
+    /// A rough estimate of the number of bytes this profile takes up.
+    pub fn estimated_size(&self) -> usize {
+        let num_children = self.nodes.len().saturating_sub(1);
+        self.nodes.len() * size_of::<CallNode>()
+            + (num_children + self.active_nodes.len()) * size_of::<usize>()
+            + self.execution_counts.len() * size_of::<u32>()
+    }
+}
+
+impl CallNode {
+    fn new(address: u64, parent: Option<usize>) -> Self {
+        Self {
+            address,
+            parent,
+            children: vec![],
+            num_calls: 0,
+            exclusive_cycles: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+
+    use super::*;
+
+    fn profile_of(program: Program) -> Profile {
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let mut profile = Profile::new(&vm_state);
+        while !vm_state.halting {
+            let address = vm_state.instruction_pointer;
+            vm_state.step().unwrap();
+            profile.record_cycle(address, &vm_state);
+        }
+        profile
+    }
+
+    fn summary<'s>(summaries: &'s [FunctionSummary], label: &str) -> &'s FunctionSummary {
+        summaries.iter().find(|s| s.label == label).unwrap()
+    }
+
+    #[test]
+    fn cycles_are_attributed_to_call_stacks() {
+        let program = triton_program!(
+            call foo call bar halt
+            foo: call bar return
+            bar: nop nop return
+        );
+        let profile = profile_of(program.clone());
+        let nodes = profile.nodes();
+        let inclusive_cycles = profile.inclusive_cycles();
+        assert!(3 == nodes[Profile::ROOT].exclusive_cycles);
+        assert!(11 == inclusive_cycles[Profile::ROOT]);
+
+        let_assert!(&[foo, bar] = &nodes[Profile::ROOT].children[..]);
+        assert!("foo" == program.label_for_address(nodes[foo].address));
+        assert!(2 == nodes[foo].exclusive_cycles);
+        assert!(5 == inclusive_cycles[foo]);
+        assert!(3 == nodes[bar].exclusive_cycles);
+
+        let_assert!(&[bar_in_foo] = &nodes[foo].children[..]);
+        assert!(nodes[bar].address == nodes[bar_in_foo].address);
+        assert!(1 == nodes[bar_in_foo].num_calls);
+
+        let summaries = profile.function_summaries(&program);
+        let bar = summary(&summaries, "bar");
+        assert!(2 == bar.num_calls);
+        assert!(6 == bar.inclusive_cycles);
+        assert!(6 == bar.exclusive_cycles);
+    }
+
+    #[test]
+    fn recursive_calls_are_counted_once_in_inclusive_cycles() {
+        let program = triton_program!(
+            push 2 call count_down halt
+            count_down:
+                dup 0 push 0 eq skiz return
+                push -1 add call count_down return
+        );
+        let profile = profile_of(program.clone());
+        let summaries = profile.function_summaries(&program);
+        let count_down = summary(&summaries, "count_down");
+        assert!(3 == count_down.num_calls);
+        assert!(count_down.exclusive_cycles == count_down.inclusive_cycles);
+
+        let root = summary(&summaries, &program.label_for_address(0));
+        let total_cycles = root.exclusive_cycles + count_down.inclusive_cycles;
+        assert!(total_cycles == root.inclusive_cycles);
+    }
+
+    #[test]
+    fn folded_stacks_list_cycles_per_call_stack() {
+        let program = triton_program!(
+            call foo call bar halt
+            foo: call bar return
+            bar: nop nop return
+        );
+        let profile = profile_of(program.clone());
+        let root = program.label_for_address(0);
+        let expected = format!("{root} 3\n{root};foo 2\n{root};foo;bar 3\n{root};bar 3\n");
+        assert!(expected == profile.folded_stacks(&program));
+    }
+
+    #[test]
+    fn speedscope_samples_refer_to_shared_frames() {
+        let program = triton_program!(call foo call bar halt foo: call bar return bar: return);
+        let profile = profile_of(program.clone());
+        let speedscope = profile.speedscope(&program, "test");
+
+        let frames = speedscope["shared"]["frames"].as_array().unwrap();
+        let frame_names = frames
+            .iter()
+            .map(|f| f["name"].as_str().unwrap())
+            .collect_vec();
+        assert!(3 == frame_names.len());
+        assert!(frame_names.contains(&"foo"));
+        assert!(frame_names.contains(&"bar"));
+
+        let sampled_profile = &speedscope["profiles"][0];
+        let samples = sampled_profile["samples"].as_array().unwrap();
+        let weights = sampled_profile["weights"].as_array().unwrap();
+        assert!(samples.len() == weights.len());
+        let total_weight = weights.iter().map(|w| w.as_u64().unwrap()).sum::<u64>();
+        assert!(sampled_profile["endValue"].as_u64() == Some(total_weight));
+
+        let last_sample = samples.last().unwrap().as_array().unwrap();
+        let innermost_frame = last_sample.last().unwrap().as_u64().unwrap();
+        assert!("bar" == frame_names[innermost_frame as usize]);
+    }
+
+    #[test]
+    fn export_format_depends_on_file_extension() {
+        let program = triton_program!(call foo halt foo: return);
+        let profile = profile_of(program.clone());
+        let directory = std::env::temp_dir().join("triton-tui-profile-export-test");
+        fs::create_dir_all(&directory).unwrap();
+
+        let json_path = directory.join("profile.json");
+        profile.export(&program, &json_path).unwrap();
+        let json = fs::read_to_string(&json_path).unwrap();
+        let_assert!(Ok(json) = serde_json::from_str::<Value>(&json));
+        assert!("profile" == json["name"]);
+
+        let folded_path = directory.join("profile.folded");
+        profile.export(&program, &folded_path).unwrap();
+        let folded = fs::read_to_string(&folded_path).unwrap();
+        assert!(folded == profile.folded_stacks(&program));
+    }
+
+    #[test]
+    fn executions_are_counted_per_instruction() {
+        let program = triton_program!(call foo call foo halt foo: nop return);
+        let profile = profile_of(program);
+        assert!(1 == profile.execution_count(0));
+        assert!(1 == profile.execution_count(4));
+        assert!(2 == profile.execution_count(5));
+        assert!(2 == profile.max_execution_count());
+        assert!(0 == profile.execution_count(1));
+        assert!(0 == profile.execution_count(100));
+    }
+
+    #[test]
+    fn repeated_calls_share_call_tree_node() {
+        let program = triton_program!(call foo call foo halt foo: return);
+        let profile = profile_of(program);
+        let_assert!(&[foo] = &profile.nodes()[Profile::ROOT].children[..]);
+        assert!(2 == profile.nodes()[foo].num_calls);
+    }
+}
@@ -0,0 +1,208 @@
+//! Generating and verifying a STARK proof of the loaded program's run. Proving takes a while,
+//! which is why it happens in the background.
+
+use std::fmt;
+use std::fmt::Display;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::bail;
+use fs_err as fs;
+use serde::Serialize;
+use triton_vm::prelude::*;
+
+/// Identifies one run of the prover. Unique for the whole session, even across resets and
+/// reloads, which allows telling a stale prover's result from the current one's.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize)]
+pub(crate) struct ProvingJob(u64);
+
+/// The steps of generating a proof, in order.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub(crate) enum ProvingPhase {
+    TracingExecution,
+    Proving,
+    Verifying,
+}
+
+/// A proof, together with everything worth knowing about it.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct ProofReport {
+    pub claim: Claim,
+    pub proof: Proof,
+    pub padded_height: usize,
+
+    /// The time it took to trace execution and generate the proof, excluding verification.
+    pub proving_time: Duration,
+    pub is_valid: bool,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) enum ProverStatus {
+    #[default]
+    Idle,
+    Running {
+        job: ProvingJob,
+        phase: ProvingPhase,
+        start: Instant,
+    },
+    Done(Box<ProofReport>),
+}
+
+impl ProvingJob {
+    pub fn next() -> Self {
+        static NEXT_JOB: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_JOB.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl ProofReport {
+    /// The file name of the proof when saved with [`Self::save`].
+    pub const PROOF_FILE_NAME: &'static str = "proof.json";
+
+    /// The file name of the claim when saved with [`Self::save`].
+    pub const CLAIM_FILE_NAME: &'static str = "claim.json";
+
+    /// The size of the proof in bytes.
+    pub fn proof_size(&self) -> usize {
+        self.proof.0.len() * size_of::<BFieldElement>()
+    }
+
+    /// Write proof and claim to the given directory. Returns the paths of both files, in that
+    /// order.
+    pub fn save(&self, directory: &Path) -> Result<(PathBuf, PathBuf)> {
+        fs::create_dir_all(directory)?;
+        let proof_path = directory.join(Self::PROOF_FILE_NAME);
+        let claim_path = directory.join(Self::CLAIM_FILE_NAME);
+        fs::write(&proof_path, serde_json::to_string(&self.proof)?)?;
+        fs::write(&claim_path, serde_json::to_string(&self.claim)?)?;
+        Ok((proof_path, claim_path))
+    }
+}
+
+impl Display for ProvingPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TracingExecution => write!(f, "tracing execution"),
+            Self::Proving => write!(f, "proving"),
+            Self::Verifying => write!(f, "verifying"),
+        }
+    }
+}
+
+impl Display for ProofReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let proof_size = self.proof_size() as f64 / 1024.0;
+        let padded_height = self.padded_height;
+        let proving_time = self.proving_time.as_secs_f64();
+        let verdict = if self.is_valid { "valid" } else { "INVALID" };
+        write!(
+            f,
+            "proof of {proof_size:.1} KiB, padded height {padded_height}, \
+            proven in {proving_time:.1}s, {verdict}"
+        )
+    }
+}
+
+/// Whether the given state is where a run of its program starts, given its inputs. Only such
+/// runs can be proven.
+pub(crate) fn is_start_of_run(vm_state: &VMState) -> bool {
+    let public_input = PublicInput::new(vm_state.public_input.clone().into());
+    let non_determinism = NonDeterminism {
+        individual_tokens: vm_state.secret_individual_tokens.clone().into(),
+        digests: vm_state.secret_digests.clone().into(),
+        ram: vm_state.ram.clone(),
+    };
+    let start_of_run = VMState::new(vm_state.program.clone(), public_input, non_determinism);
+    *vm_state == start_of_run
+}
+
+/// Prove the run of the program starting in the given state, then verify the proof. Reports
+/// every phase as it begins. Blocks until done.
+pub(crate) fn prove(
+    initial_state: VMState,
+    mut report_phase: impl FnMut(ProvingPhase),
+) -> Result<ProofReport> {
+    if !is_start_of_run(&initial_state) {
+        bail!("can only prove a run starting at the beginning of the program");
+    }
+
+    let start = Instant::now();
+    report_phase(ProvingPhase::TracingExecution);
+    let claim = Claim::about_program(&initial_state.program)
+        .with_input(Vec::from(initial_state.public_input.clone()));
+    let (aet, final_state) = VM::trace_execution_of_state(initial_state)?;
+    let claim = claim.with_output(final_state.public_output);
+
+    report_phase(ProvingPhase::Proving);
+    let stark = Stark::default();
+    let proof = stark.prove(&claim, &aet)?;
+    let proving_time = start.elapsed();
+
+    report_phase(ProvingPhase::Verifying);
+    let is_valid = triton_vm::verify(stark, &claim, &proof);
+    let padded_height = proof.padded_height()?;
+
+    Ok(ProofReport {
+        claim,
+        proof,
+        padded_height,
+        proving_time,
+        is_valid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use assert2::assert;
+    use assert2::let_assert;
+
+    use super::*;
+
+    #[test]
+    fn proof_of_halting_program_is_valid() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let vm_state = VMState::new(program, bfe_vec![21].into(), [].into());
+        let mut phases = vec![];
+        let_assert!(Ok(report) = prove(vm_state, |phase| phases.push(phase)));
+        assert!(report.is_valid);
+        assert!(bfe_vec![42] == report.claim.output);
+        assert!(report.padded_height.is_power_of_two());
+        let expected_phases = [
+            ProvingPhase::TracingExecution,
+            ProvingPhase::Proving,
+            ProvingPhase::Verifying,
+        ];
+        assert!(expected_phases.as_slice() == phases);
+
+        let directory = temp_dir().join("triton_tui_proof");
+        let_assert!(Ok((proof_path, claim_path)) = report.save(&directory));
+        let proof = serde_json::from_str::<Proof>(&fs::read_to_string(proof_path).unwrap());
+        let claim = serde_json::from_str::<Claim>(&fs::read_to_string(claim_path).unwrap());
+        assert!(Ok(report.proof) == proof.map_err(|e| e.to_string()));
+        assert!(Ok(report.claim) == claim.map_err(|e| e.to_string()));
+    }
+
+    #[test]
+    fn failing_program_cannot_be_proven() {
+        let program = triton_program!(push 0 assert halt);
+        let vm_state = VMState::new(program, [].into(), [].into());
+        let_assert!(Err(_) = prove(vm_state, |_| ()));
+    }
+
+    #[test]
+    fn only_runs_from_the_beginning_can_be_proven() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        assert!(is_start_of_run(&vm_state));
+        vm_state.step().unwrap();
+        assert!(!is_start_of_run(&vm_state));
+        let_assert!(Err(_) = prove(vm_state, |_| ()));
+    }
+}
@@ -0,0 +1,157 @@
+//! Reloading the program and its inputs when their files change, and getting back to where
+//! execution was before reloading.
+
+use std::fmt;
+use std::fmt::Display;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use fs_err as fs;
+use triton_vm::prelude::*;
+
+use crate::args::InputArgs;
+
+/// Watches the files of the program, the public input, and the non-determinism by polling
+/// their modification times.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct FileWatcher {
+    /// The watched files, each with the modification time it had when last checked. The time is
+    /// `None` if the file could not be inspected, for example because it did not exist.
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+/// Where execution is, in terms that survive reloading the program.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ExecutionPosition {
+    pub cycle: u32,
+
+    /// The label of the function being executed, and the call depth. `None` outside of any
+    /// function.
+    pub function: Option<(String, usize)>,
+}
+
+impl FileWatcher {
+    pub fn new(input_args: &InputArgs) -> Self {
+        let paths = [
+            Some(&input_args.program),
+            input_args.input.as_ref(),
+            input_args.non_determinism.as_ref(),
+        ];
+        let files = paths
+            .into_iter()
+            .flatten()
+            .map(PathBuf::from)
+            .map(|path| {
+                let modification_time = Self::modification_time(&path);
+                (path, modification_time)
+            })
+            .collect();
+        Self { files }
+    }
+
+    fn modification_time(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// Whether any of the watched files changed since the previous check.
+    pub fn files_changed(&mut self) -> bool {
+        let mut files_changed = false;
+        for (path, last_modification_time) in &mut self.files {
+            let modification_time = Self::modification_time(path);
+            if modification_time != *last_modification_time {
+                *last_modification_time = modification_time;
+                files_changed = true;
+            }
+        }
+        files_changed
+    }
+}
+
+impl ExecutionPosition {
+    pub fn of(vm_state: &VMState) -> Self {
+        let function = vm_state.jump_stack.last().map(|&(_, destination)| {
+            let label = vm_state.program.label_for_address(destination.value());
+            (label, vm_state.jump_stack.len())
+        });
+        Self {
+            cycle: vm_state.cycle_count,
+            function,
+        }
+    }
+
+    /// Whether the given state is at this position. For an unchanged program, the cycle count
+    /// pins down the position. If the program changed, cycle counts are not comparable, and the
+    /// position is reached once the same function executes at the same call depth for the
+    /// first time. Outside of any function, the cycle count is used regardless.
+    pub fn is_reached_by(&self, vm_state: &VMState, is_same_program: bool) -> bool {
+        if is_same_program || self.function.is_none() {
+            return vm_state.cycle_count >= self.cycle;
+        }
+        Self::of(vm_state).function == self.function
+    }
+}
+
+impl Display for ExecutionPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle {}", self.cycle)?;
+        if let Some((ref label, depth)) = self.function {
+            write!(f, " in “{label}” at call depth {depth}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use assert2::assert;
+
+    use super::*;
+
+    fn step(vm_state: &mut VMState, num_steps: usize) {
+        for _ in 0..num_steps {
+            vm_state.step().unwrap();
+        }
+    }
+
+    #[test]
+    fn changed_file_is_noticed_once() {
+        let path = temp_dir().join("triton_tui_watched_program.tasm");
+        fs::write(&path, "halt").unwrap();
+        let input_args = InputArgs {
+            program: path.display().to_string(),
+            input: None,
+            non_determinism: None,
+        };
+        let mut watcher = FileWatcher::new(&input_args);
+        assert!(!watcher.files_changed());
+
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.files_changed());
+        assert!(!watcher.files_changed());
+
+        fs::write(&path, "push 1 halt").unwrap();
+        assert!(watcher.files_changed());
+        assert!(!watcher.files_changed());
+    }
+
+    #[test]
+    fn position_in_changed_program_is_identified_by_function_and_call_depth() {
+        let program = triton_program!(push 1 call foo halt foo: call bar return bar: return);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        step(&mut vm_state, 3);
+        let position = ExecutionPosition::of(&vm_state);
+        assert!(Some(("bar".to_string(), 2)) == position.function);
+        assert!("cycle 3 in “bar” at call depth 2" == position.to_string());
+
+        let program = triton_program!(push 1 push 2 call foo halt foo: call bar return bar: return);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        step(&mut vm_state, 3);
+        assert!(position.is_reached_by(&vm_state, true));
+        assert!(!position.is_reached_by(&vm_state, false));
+        step(&mut vm_state, 1);
+        assert!(position.is_reached_by(&vm_state, false));
+    }
+}
@@ -0,0 +1,270 @@
+//! Scripts of debugger commands, run against a [`TritonVMState`] before or instead of the
+//! interactive user interface. Scripts make debugging sessions reproducible, for example in
+//! regression tests or bug reports.
+
+use std::fmt;
+use std::fmt::Display;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
+use fs_err as fs;
+
+use crate::action::Action;
+use crate::action::Execute;
+use crate::command;
+use crate::components::Component;
+use crate::condition::Condition;
+use crate::triton_vm_state::TritonVMState;
+
+/// A list of debugger commands, one per line. Empty lines and lines starting with `//` are
+/// ignored. Available commands are
+/// - `continue`, `step`, `next`, and `finish`, as well as `reverse-continue`, `reverse-step`,
+///   `reverse-next`, and `reverse-finish`, each followed by an optional repeat count,
+/// - `undo` and `redo`,
+/// - `expect <condition>` to check that the condition holds in the current state, and
+/// - all commands of the command line, like `dump <file>`; see [`command::parse`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Script {
+    /// The commands, each with the 1-based number of the line it was given on.
+    commands: Vec<(usize, ScriptCommand)>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum ScriptCommand {
+    Action(Action),
+    Expect(Condition),
+}
+
+/// An `expect` command whose condition did not hold, or could not be evaluated.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct FailedExpectation {
+    pub line: usize,
+    pub condition: Condition,
+    pub cycle: u32,
+    pub instruction_pointer: usize,
+
+    /// Why the condition could not be evaluated, if it could not.
+    pub evaluation_error: Option<String>,
+}
+
+impl Script {
+    pub fn load(path: &str) -> Result<Self> {
+        let source = fs::read_to_string(path)?;
+        Self::parse(&source).map_err(|err| anyhow!("script “{path}”, {err}"))
+    }
+
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut commands = vec![];
+        for (line_index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            let line_number = line_index + 1;
+            let command =
+                ScriptCommand::parse(line).map_err(|err| anyhow!("line {line_number}: {err}"))?;
+            commands.push((line_number, command));
+        }
+        Ok(Self { commands })
+    }
+
+    /// Run all commands against the given state. Returns the expectations that failed. Fails if
+    /// a command cannot be carried out, for example because a breakpoint refers to a label that
+    /// does not exist.
+    pub fn run(&self, state: &mut TritonVMState) -> Result<Vec<FailedExpectation>> {
+        let mut failed_expectations = vec![];
+        for (line, command) in &self.commands {
+            let line = *line;
+            match command {
+                ScriptCommand::Action(action @ Action::Execute(_)) => {
+                    state.update(action.clone())?;
+                }
+                ScriptCommand::Action(action) => {
+                    state.warning = None;
+                    state.update(action.clone())?;
+                    if let Some(warning) = state.warning.take() {
+                        bail!("line {line}: {warning}");
+                    }
+                }
+                ScriptCommand::Expect(condition) => {
+                    let (holds, evaluation_error) = match condition.evaluate(state) {
+                        Ok(holds) => (holds, None),
+                        Err(err) => (false, Some(err.to_string())),
+                    };
+                    if !holds {
+                        failed_expectations.push(FailedExpectation {
+                            line,
+                            condition: condition.clone(),
+                            cycle: state.vm_state.cycle_count,
+                            instruction_pointer: state.vm_state.instruction_pointer,
+                            evaluation_error,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(failed_expectations)
+    }
+}
+
+impl ScriptCommand {
+    fn parse(line: &str) -> Result<Self> {
+        let (name, arguments) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let arguments = arguments.trim();
+
+        let execute = match name {
+            "continue" | "c" => Execute::Continue,
+            "step" | "s" => Execute::Step,
+            "next" | "n" => Execute::Next,
+            "finish" | "f" => Execute::Finish,
+            "reverse-continue" => Execute::ReverseContinue,
+            "reverse-step" => Execute::ReverseStep,
+            "reverse-next" => Execute::ReverseNext,
+            "reverse-finish" => Execute::ReverseFinish,
+            "undo" | "redo" if !arguments.is_empty() => {
+                bail!("unexpected arguments after “{name}”")
+            }
+            "undo" => return Ok(Self::Action(Action::Undo)),
+            "redo" => return Ok(Self::Action(Action::Redo)),
+            "expect" => return Ok(Self::Expect(Condition::parse(arguments)?)),
+            _ => return Ok(Self::Action(command::parse(line)?)),
+        };
+        let count = Self::parse_repeat_count(arguments)?;
+        Ok(Self::Action(Action::Execute(execute(count))))
+    }
+
+    fn parse_repeat_count(count: &str) -> Result<u32> {
+        if count.is_empty() {
+            return Ok(1);
+        }
+        match count.parse() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => bail!("invalid repeat count “{count}”"),
+        }
+    }
+}
+
+impl Display for FailedExpectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            line,
+            condition,
+            cycle,
+            instruction_pointer: ip,
+            ..
+        } = self;
+        write!(
+            f,
+            "line {line}: expected “{condition}” at cycle {cycle}, ip {ip}"
+        )?;
+        match &self.evaluation_error {
+            Some(err) => write!(f, ", but {err}"),
+            None => write!(f, ", but it does not hold"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use assert2::assert;
+    use assert2::let_assert;
+    use triton_vm::prelude::*;
+
+    use crate::args::TuiArgs;
+    use crate::command::Location;
+
+    use super::*;
+
+    #[test]
+    fn parse_execution_commands_with_and_without_repeat_count() {
+        let_assert!(Ok(script) = Script::parse("continue\n  step 10\n\n// comment\nfinish 2"));
+        let expected = vec![
+            (
+                1,
+                ScriptCommand::Action(Action::Execute(Execute::Continue(1))),
+            ),
+            (2, ScriptCommand::Action(Action::Execute(Execute::Step(10)))),
+            (
+                5,
+                ScriptCommand::Action(Action::Execute(Execute::Finish(2))),
+            ),
+        ];
+        assert!(expected == script.commands);
+    }
+
+    #[test]
+    fn parse_command_line_commands() {
+        let_assert!(Ok(script) = Script::parse("break my_loop"));
+        let location = Location::Label("my_loop".to_string());
+        let expected = ScriptCommand::Action(Action::SetBreakpoint(location, None));
+        assert!(vec![(1, expected)] == script.commands);
+    }
+
+    #[test]
+    fn parse_errors_mention_line_number() {
+        let_assert!(Err(err) = Script::parse("step\nstep 0"));
+        assert!(err.to_string().contains("line 2"));
+
+        for malformed in ["expect", "expect st0", "step x", "undo 3", "frobnicate"] {
+            let_assert!(Err(_) = Script::parse(malformed), "{malformed}");
+        }
+    }
+
+    #[test]
+    fn failed_expectations_are_reported_with_cycle_and_instruction_pointer() {
+        let program = triton_program!(
+            push 41 push 1 add call my_loop halt
+            my_loop: push 0 pop 1 return
+        );
+        let vm_state = VMState::new(program, [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        let script = "
+            step 2
+            expect st0 == 1
+            break my_loop
+            continue
+            expect st0 == 42
+            expect st0 == 43
+        ";
+        let_assert!(Ok(script) = Script::parse(script));
+        let_assert!(Ok(failed_expectations) = script.run(&mut state));
+        let_assert!([failed] = failed_expectations.as_slice());
+        assert!(7 == failed.line);
+        assert!(4 == failed.cycle);
+        assert!(8 == failed.instruction_pointer);
+        let message = failed.to_string();
+        assert!(message.starts_with("line 7: expected “st0 == 43” at cycle 4, ip 8"));
+    }
+
+    #[test]
+    fn commands_that_cannot_be_carried_out_abort_the_script() {
+        let vm_state = VMState::new(triton_program!(halt), [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        let_assert!(Ok(script) = Script::parse("break no_such_label\nstep"));
+        let_assert!(Err(err) = script.run(&mut state));
+        assert!(err.to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn dumped_state_can_be_loaded_as_initial_state() {
+        let program = triton_program!(push 1 push 2 halt);
+        let vm_state = VMState::new(program, [].into(), [].into());
+        let mut state = TritonVMState::from_vm_state(vm_state, None, &TuiArgs::default());
+        let path = temp_dir().join("triton_tui_script_dump.json");
+        let path = path.display().to_string();
+        let_assert!(Ok(script) = Script::parse(&format!("step\ndump --hints {path}")));
+        let_assert!(Ok(_) = script.run(&mut state));
+
+        let args = TuiArgs {
+            input_args: None,
+            initial_state: Some(path),
+            ..TuiArgs::default()
+        };
+        let_assert!(Ok(loaded) = TritonVMState::new(&args));
+        assert!(state.vm_state == loaded.vm_state);
+        assert!(state.type_hints == loaded.type_hints);
+    }
+}
@@ -0,0 +1,176 @@
+//! Recording the actions of a user to a session file, and replaying them. Useful for demos,
+//! teaching, and reproducing bugs that depend on the exact order of actions.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::time::Instant;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::anyhow;
+use fs_err as fs;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::action::Action;
+use crate::action::Execute;
+use crate::action::Toggle;
+use crate::mode::Mode;
+
+/// The user-driven [`Action`]s, the ones that are recorded in a session.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) enum SessionAction {
+    Execute(Execute),
+    Undo,
+    Redo,
+    Reset,
+    Toggle(Toggle),
+    Mode(Mode),
+}
+
+/// One line of a session file.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RecordedAction {
+    /// Milliseconds since recording started.
+    pub elapsed_ms: u64,
+    pub action: SessionAction,
+}
+
+/// Writes user-driven actions to a session file, one JSON object per line. Every action is
+/// written immediately, so that the session survives a crash.
+#[derive(Debug)]
+pub(crate) struct SessionRecorder {
+    start: Instant,
+    file: fs::File,
+}
+
+/// The recorded actions still to be replayed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Replay {
+    actions: VecDeque<SessionAction>,
+
+    /// If set, every action is replayed on request only. Otherwise, the whole session is
+    /// replayed at full speed.
+    pub stepwise: bool,
+}
+
+impl SessionAction {
+    pub fn from_action(action: &Action) -> Option<Self> {
+        let session_action = match action {
+            Action::Execute(execute) => Self::Execute(execute.clone()),
+            Action::Undo => Self::Undo,
+            Action::Redo => Self::Redo,
+            Action::Reset => Self::Reset,
+            Action::Toggle(toggle) => Self::Toggle(*toggle),
+            Action::Mode(mode) => Self::Mode(*mode),
+            _ => return None,
+        };
+        Some(session_action)
+    }
+}
+
+impl From<SessionAction> for Action {
+    fn from(action: SessionAction) -> Self {
+        match action {
+            SessionAction::Execute(execute) => Action::Execute(execute),
+            SessionAction::Undo => Action::Undo,
+            SessionAction::Redo => Action::Redo,
+            SessionAction::Reset => Action::Reset,
+            SessionAction::Toggle(toggle) => Action::Toggle(toggle),
+            SessionAction::Mode(mode) => Action::Mode(mode),
+        }
+    }
+}
+
+impl SessionRecorder {
+    pub fn new(path: &str) -> Result<Self> {
+        let file = fs::File::create(path)?;
+        let start = Instant::now();
+        Ok(Self { start, file })
+    }
+
+    /// Record the given action if it is user-driven, and ignore it otherwise.
+    pub fn record(&mut self, action: &Action) -> Result<()> {
+        let Some(action) = SessionAction::from_action(action) else {
+            return Ok(());
+        };
+        let elapsed_ms = u64::try_from(self.start.elapsed().as_millis())?;
+        let recorded_action = RecordedAction { elapsed_ms, action };
+        let line = serde_json::to_string(&recorded_action)?;
+        writeln!(self.file, "{line}")?;
+        Ok(())
+    }
+}
+
+impl Replay {
+    pub fn load(path: &str, stepwise: bool) -> Result<Self> {
+        let file = fs::File::open(path)?;
+        let mut actions = VecDeque::new();
+        for (line_index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let recorded_action = serde_json::from_str::<RecordedAction>(&line)
+                .map_err(|err| anyhow!("session “{path}”, line {}: {err}", line_index + 1))?;
+            actions.push_back(recorded_action.action);
+        }
+        Ok(Self { actions, stepwise })
+    }
+
+    pub fn next_action(&mut self) -> Option<Action> {
+        self.actions.pop_front().map(Action::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use assert2::assert;
+    use assert2::let_assert;
+
+    use crate::command::Location;
+
+    use super::*;
+
+    #[test]
+    fn only_user_driven_actions_are_recorded() {
+        let path = temp_dir().join("triton_tui_session_recording.jsonl");
+        let path = path.display().to_string();
+        let_assert!(Ok(mut recorder) = SessionRecorder::new(&path));
+        let actions = [
+            Action::Tick,
+            Action::Execute(Execute::Step(3)),
+            Action::Render,
+            Action::Toggle(Toggle::Heatmap),
+            Action::RecordUndoInfo,
+            Action::Mode(Mode::Memory),
+            Action::Execute(Execute::RunTo(Location::Label("main".to_string()))),
+            Action::Undo,
+            Action::Reset,
+        ];
+        for action in &actions {
+            let_assert!(Ok(()) = recorder.record(action));
+        }
+        drop(recorder);
+
+        let_assert!(Ok(mut replay) = Replay::load(&path, false));
+        let replayed_actions = std::iter::from_fn(|| replay.next_action()).collect::<Vec<_>>();
+        let expected_actions = actions
+            .into_iter()
+            .filter(|action| SessionAction::from_action(action).is_some())
+            .collect::<Vec<_>>();
+        assert!(expected_actions == replayed_actions);
+    }
+
+    #[test]
+    fn malformed_session_file_is_rejected_with_line_number() {
+        let path = temp_dir().join("triton_tui_session_malformed.jsonl");
+        let session = "{\"elapsed_ms\":0,\"action\":\"Undo\"}\n{\"action\":\"Frobnicate\"}\n";
+        fs::write(&path, session).unwrap();
+        let_assert!(Err(err) = Replay::load(&path.display().to_string(), true));
+        assert!(err.to_string().contains("line 2"));
+    }
+}
@@ -4,6 +4,8 @@ use std::iter::once;
 use color_eyre::eyre::Result;
 use color_eyre::eyre::bail;
 use itertools::Itertools;
+use serde::Deserialize;
+use serde::Serialize;
 use triton_vm::isa::instruction::*;
 use triton_vm::isa::op_stack::NumberOfWords;
 use triton_vm::isa::op_stack::*;
@@ -16,7 +18,7 @@ pub(crate) type TopOfStack = [BFieldElement; NUM_OP_STACK_REGISTERS];
 
 /// Mimics the behavior of the actual memory. Helps debugging programs written for Triton VM by
 /// tracking (manually set) type hints next to stack or RAM elements.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub(crate) struct ShadowMemory {
     /// Shadow stack mimicking the actual stack.
     pub stack: Vec<Option<ElementTypeHint>>,
@@ -371,6 +373,13 @@ mod tests {
         assert!(expected_stack_length == actual_stack_length);
     }
 
+    #[proptest]
+    fn shadow_memory_survives_json_roundtrip(type_hints: ShadowMemory) {
+        let json = serde_json::to_string(&type_hints)?;
+        let deserialized_type_hints = serde_json::from_str::<ShadowMemory>(&json)?;
+        prop_assert_eq!(type_hints, deserialized_type_hints);
+    }
+
     #[proptest]
     fn type_hint_stack_grows_and_shrinks_like_actual_stack(
         mut type_hints: ShadowMemory,
@@ -0,0 +1,349 @@
+use std::cmp::max;
+use std::collections::HashSet;
+use std::mem::size_of;
+
+use strum::IntoEnumIterator;
+use triton_vm::aet::AlgebraicExecutionTrace;
+use triton_vm::air::table::hash::MONTGOMERY_MODULUS;
+use triton_vm::air::table::hash::PERMUTATION_TRACE_LENGTH;
+use triton_vm::air::table::hash::PermutationTrace;
+use triton_vm::isa::instruction::Instruction;
+use triton_vm::prelude::twenty_first::math::x_field_element::EXTENSION_DEGREE;
+use triton_vm::prelude::*;
+use triton_vm::table::u32::U32TableEntry;
+use triton_vm::vm::CoProcessorCall;
+
+/// The heights of the tables making up the Algebraic Execution Trace (AET), tracked one
+/// executed instruction at a time. The heights determine the cost of proving correct execution.
+///
+/// Only instructions executed after creation count towards the heights. For example, the
+/// processor table's height is the number of recorded cycles.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct TableHeights {
+    program: usize,
+    processor: usize,
+    op_stack: usize,
+    ram: usize,
+    hash: usize,
+    u32: usize,
+
+    /// The distinct 16-bit limbs looked up by the hash table. Each adds one row to the cascade
+    /// table.
+    cascade_limbs: HashSet<u16>,
+
+    /// The distinct executed u32 instructions, including their operands. Repeated executions do
+    /// not add to the u32 table.
+    u32_entries: HashSet<U32TableEntry>,
+}
+
+impl TableHeights {
+    /// The lookup table always contains all 8-bit values.
+    const LOOKUP_TABLE_HEIGHT: usize = 1 << 8;
+
+    /// The most rows one u32 instruction can add to the u32 table. See
+    /// [`Self::u32_table_height_contribution`].
+    const MAX_U32_TABLE_HEIGHT_CONTRIBUTION: usize = 1 + u64::BITS as usize;
+
+    /// The table heights before executing any instruction. Hashing the program adds to the hash
+    /// and cascade tables.
+    pub fn new(program: &Program) -> Self {
+        let aet = AlgebraicExecutionTrace::new(program.clone());
+        let cascade_limbs = aet
+            .cascade_table_lookup_multiplicities
+            .keys()
+            .copied()
+            .collect();
+
+        Self {
+            program: aet.height_of_table(TableId::Program),
+            processor: 0,
+            op_stack: 0,
+            ram: 0,
+            hash: aet.height_of_table(TableId::Hash),
+            u32: 0,
+            cascade_limbs,
+            u32_entries: HashSet::new(),
+        }
+    }
+
+    /// Account for one executed instruction, given the co-processor calls it resulted in.
+    pub fn record(&mut self, co_processor_calls: Vec<CoProcessorCall>) {
+        self.processor += 1;
+        for call in co_processor_calls {
+            match call {
+                CoProcessorCall::SpongeStateReset => self.hash += 1,
+                CoProcessorCall::Tip5Trace(_, trace) => {
+                    self.hash += trace.len();
+                    self.cascade_limbs.extend(Self::cascade_limbs_of(&trace));
+                }
+                CoProcessorCall::U32(entry) => {
+                    if self.u32_entries.insert(entry) {
+                        self.u32 += Self::u32_table_height_contribution(entry);
+                    }
+                }
+                CoProcessorCall::OpStack(_) => self.op_stack += 1,
+                CoProcessorCall::Ram(_) => self.ram += 1,
+            }
+        }
+    }
+
+    /// The 16-bit limbs the hash table looks up in the cascade table for the given trace.
+    fn cascade_limbs_of(trace: &PermutationTrace) -> impl Iterator<Item = u16> + '_ {
+        let (_, rows_with_lookups) = trace.split_last().unwrap();
+        rows_with_lookups
+            .iter()
+            .flat_map(|row| &row[..tip5::NUM_SPLIT_AND_LOOKUP])
+            .flat_map(|&element| Self::lookup_limbs(element))
+    }
+
+    /// The 16-bit limbs of a state element that the hash table looks up in the cascade table.
+    fn lookup_limbs(element: BFieldElement) -> [u16; 4] {
+        let montgomery_representation = (MONTGOMERY_MODULUS * element).value();
+        [0, 16, 32, 48].map(|shift| (montgomery_representation >> shift) as u16)
+    }
+
+    fn u32_table_height_contribution(entry: U32TableEntry) -> usize {
+        let left_operand = entry.left_operand.value();
+        let right_operand = entry.right_operand.value();
+        let dominant_operand = match entry.instruction {
+            Instruction::Pow => right_operand,
+            _ => max(left_operand, right_operand),
+        };
+        match dominant_operand {
+            0 => 1,
+            _ => 2 + dominant_operand.ilog2() as usize,
+        }
+    }
+
+    pub fn height(&self, table: TableId) -> usize {
+        match table {
+            TableId::Program => self.program,
+            TableId::Processor | TableId::JumpStack => self.processor,
+            TableId::OpStack => self.op_stack,
+            TableId::Ram => self.ram,
+            TableId::Hash => self.hash,
+            TableId::Cascade => self.cascade_limbs.len(),
+            TableId::Lookup => Self::LOOKUP_TABLE_HEIGHT,
+            TableId::U32 => self.u32,
+        }
+    }
+
+    /// The highest table. Determines the padded height. Of equally high tables, the one listed
+    /// first in [`TableId`] dominates.
+    pub fn dominant_table(&self) -> TableId {
+        TableId::iter()
+            .rev()
+            .max_by_key(|&t| self.height(t))
+            .unwrap()
+    }
+
+    /// The height all tables are padded to for proving: the next power of two.
+    pub fn padded_height(&self) -> usize {
+        self.height(self.dominant_table()).next_power_of_two()
+    }
+
+    /// The padded height after executing the next instruction in the given state, or `None` if
+    /// that instruction might grow the padded height but cannot be executed.
+    ///
+    /// Most instructions cannot possibly grow the padded height, which is known from the
+    /// instruction alone. Only the others are executed, on a copy of the VM state.
+    pub fn padded_height_after_next_instruction(&self, vm_state: &VMState) -> Option<usize> {
+        let padded_height = self.padded_height();
+        let Ok(instruction) = vm_state.current_instruction() else {
+            return Some(padded_height);
+        };
+        let max_height_after_instruction = TableId::iter()
+            .map(|table| self.height(table) + Self::max_growth(instruction, table))
+            .max()
+            .unwrap();
+        if max_height_after_instruction <= padded_height {
+            return Some(padded_height);
+        }
+
+        let co_processor_calls = vm_state.clone().step().ok()?;
+        Some(self.padded_height_after(&co_processor_calls))
+    }
+
+    /// An upper bound on the number of rows executing the given instruction adds to the table.
+    fn max_growth(instruction: Instruction, table: TableId) -> usize {
+        let num_tip5_traces = match instruction {
+            Instruction::Hash
+            | Instruction::SpongeAbsorb
+            | Instruction::SpongeAbsorbMem
+            | Instruction::SpongeSqueeze
+            | Instruction::MerkleStep
+            | Instruction::MerkleStepMem => 1,
+            _ => 0,
+        };
+        match table {
+            TableId::Program | TableId::Lookup => 0,
+            TableId::Processor | TableId::JumpStack => 1,
+            TableId::OpStack => instruction.op_stack_size_influence().unsigned_abs() as usize,
+            TableId::Ram => match instruction {
+                Instruction::ReadMem(n) | Instruction::WriteMem(n) => n.num_words(),
+                Instruction::SpongeAbsorbMem => tip5::RATE,
+                Instruction::MerkleStepMem => Digest::LEN,
+                Instruction::XxDotStep => 2 * EXTENSION_DEGREE,
+                Instruction::XbDotStep => 1 + EXTENSION_DEGREE,
+                _ => 0,
+            },
+            TableId::Hash => match instruction {
+                Instruction::SpongeInit => 1,
+                _ => num_tip5_traces * PERMUTATION_TRACE_LENGTH,
+            },
+            TableId::Cascade => {
+                let num_lookups = (PERMUTATION_TRACE_LENGTH - 1) * tip5::NUM_SPLIT_AND_LOOKUP;
+                num_tip5_traces * num_lookups * 4
+            }
+            TableId::U32 => match instruction {
+                Instruction::DivMod => 2 * Self::MAX_U32_TABLE_HEIGHT_CONTRIBUTION,
+                _ if instruction.is_u32_instruction() => Self::MAX_U32_TABLE_HEIGHT_CONTRIBUTION,
+                _ => 0,
+            },
+        }
+    }
+
+    /// The padded height after accounting for one more instruction, given the co-processor
+    /// calls it resulted in. Unlike [`Self::record`], leaves the table heights unchanged.
+    fn padded_height_after(&self, co_processor_calls: &[CoProcessorCall]) -> usize {
+        let mut op_stack = 0;
+        let mut ram = 0;
+        let mut hash = 0;
+        let mut u32 = 0;
+        let mut new_cascade_limbs = HashSet::new();
+        let mut new_u32_entries = HashSet::new();
+        for call in co_processor_calls {
+            match call {
+                CoProcessorCall::SpongeStateReset => hash += 1,
+                CoProcessorCall::Tip5Trace(_, trace) => {
+                    hash += trace.len();
+                    let new_limbs = Self::cascade_limbs_of(trace)
+                        .filter(|limb| !self.cascade_limbs.contains(limb));
+                    new_cascade_limbs.extend(new_limbs);
+                }
+                CoProcessorCall::U32(entry) => {
+                    if !self.u32_entries.contains(entry) && new_u32_entries.insert(*entry) {
+                        u32 += Self::u32_table_height_contribution(*entry);
+                    }
+                }
+                CoProcessorCall::OpStack(_) => op_stack += 1,
+                CoProcessorCall::Ram(_) => ram += 1,
+            }
+        }
+
+        let heights = [
+            self.program,
+            self.processor + 1,
+            self.op_stack + op_stack,
+            self.ram + ram,
+            self.hash + hash,
+            self.cascade_limbs.len() + new_cascade_limbs.len(),
+            Self::LOOKUP_TABLE_HEIGHT,
+            self.u32 + u32,
+        ];
+        heights.into_iter().max().unwrap().next_power_of_two()
+    }
+
+    /// A rough estimate of the number of bytes these table heights take up.
+    pub fn estimated_size(&self) -> usize {
+        size_of::<Self>()
+            + self.cascade_limbs.len() * size_of::<u16>()
+            + self.u32_entries.len() * size_of::<U32TableEntry>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+
+    use super::*;
+
+    fn heights_after_running(program: &Program, public_input: PublicInput) -> TableHeights {
+        let mut vm_state = VMState::new(program.clone(), public_input, [].into());
+        let mut table_heights = TableHeights::new(program);
+        while !vm_state.halting {
+            table_heights.record(vm_state.step().unwrap());
+        }
+        table_heights
+    }
+
+    #[test]
+    fn table_heights_match_those_of_algebraic_execution_trace() {
+        let code = "
+            read_io 2 split pop 2
+            push 2 push 3 pow pop 1
+            push 5 push 1000 lt pop 1
+            push 7 push 1000 and pop 1
+            push 1 push 42 write_mem 1 pop 1
+            push 42 read_mem 1 pop 2
+            push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0
+            hash pop 5
+            sponge_init
+            push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0
+            sponge_absorb sponge_squeeze pop 5 pop 5
+            call foo halt
+            foo: push 1 push 2 xor pop 1 return
+        ";
+        let program = Program::from_code(code).unwrap();
+        let public_input = PublicInput::new(bfe_vec![3, 1_u64 << 40]);
+        let table_heights = heights_after_running(&program, public_input.clone());
+
+        let_assert!(Ok((aet, _)) = VM::trace_execution(program, public_input, [].into()));
+        for table in TableId::iter() {
+            assert!(
+                aet.height_of_table(table) == table_heights.height(table),
+                "{table}"
+            );
+        }
+        assert!(aet.padded_height() == table_heights.padded_height());
+    }
+
+    #[test]
+    fn dominant_table_changes_as_execution_progresses() {
+        let program = triton_program!(halt);
+        let table_heights = heights_after_running(&program, [].into());
+        assert!(TableId::Lookup == table_heights.dominant_table());
+        assert!(256 == table_heights.padded_height());
+
+        let program = triton_program!(
+            push 300 call count_down halt
+            count_down: dup 0 push 0 eq skiz return push -1 add recurse
+        );
+        let table_heights = heights_after_running(&program, [].into());
+        assert!(TableId::Processor == table_heights.dominant_table());
+    }
+
+    #[test]
+    fn padded_height_after_next_instruction_is_anticipated() {
+        let code = "
+            push 2 push 3 pow pop 1
+            push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0 push 0
+            hash pop 5
+            push 300 call count_down halt
+            count_down: dup 0 push 0 eq skiz return push -1 add recurse
+        ";
+        let program = Program::from_code(code).unwrap();
+        let mut vm_state = VMState::new(program.clone(), [].into(), [].into());
+        let mut table_heights = TableHeights::new(&program);
+        let mut padded_height_grew = false;
+        while !vm_state.halting {
+            let anticipated = table_heights.padded_height_after_next_instruction(&vm_state);
+            let padded_height = table_heights.padded_height();
+            table_heights.record(vm_state.step().unwrap());
+            assert!(Some(table_heights.padded_height()) == anticipated);
+            padded_height_grew |= table_heights.padded_height() > padded_height;
+        }
+        assert!(padded_height_grew);
+    }
+
+    #[test]
+    fn failing_next_instruction_is_anticipated_only_if_it_matters() {
+        let program = triton_program!(pop 1 halt);
+        let vm_state = VMState::new(program.clone(), [].into(), [].into());
+        let table_heights = TableHeights::new(&program);
+        let padded_height = table_heights.padded_height();
+        let anticipated = table_heights.padded_height_after_next_instruction(&vm_state);
+        assert!(Some(padded_height) == anticipated);
+    }
+}
@@ -1,4 +1,5 @@
 use color_eyre::eyre::Result;
+use color_eyre::eyre::anyhow;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -11,14 +12,25 @@ use tokio::sync::mpsc::UnboundedSender;
 use crate::action::*;
 use crate::args::TuiArgs;
 use crate::components::Component;
+use crate::components::checkpoints::Checkpoints;
 use crate::components::help::Help;
 use crate::components::home::Home;
+use crate::components::input_editor::InputEditor;
 use crate::components::memory::Memory;
+use crate::components::profiler::Profiler;
+use crate::components::verifier::Verifier;
 use crate::config::Config;
 use crate::config::KeyEvents;
+use crate::config::split_repeat_count;
 use crate::mode::Mode;
+use crate::reload::ExecutionPosition;
+use crate::reload::FileWatcher;
+use crate::script::Script;
+use crate::session::Replay;
+use crate::session::SessionRecorder;
 use crate::triton_vm_state::TritonVMState;
 use crate::tui::*;
+use crate::verifier::Verification;
 
 const RECENT_KEY_EVENTS_RESET_DELAY: u32 = 1;
 
@@ -37,6 +49,10 @@ pub(crate) struct TritonTUI {
     pub recent_key_events: KeyEvents,
 
     pub vm_state: TritonVMState,
+
+    pub session_recorder: Option<SessionRecorder>,
+    pub replay: Option<Replay>,
+    pub file_watcher: Option<FileWatcher>,
 }
 
 impl TritonTUI {
@@ -44,14 +60,34 @@ impl TritonTUI {
         let tui = Self::tui(&args)?;
         let config = Config::new()?;
 
-        let mode = Mode::default();
+        let mut vm_state = TritonVMState::new(&args)?;
+        Self::set_catchpoints_from_config(&mut vm_state, &config)?;
+
+        let verification = Self::verification(&args, &vm_state)?;
+        let mode = match verification {
+            Some(_) => Mode::Verifier,
+            None => Mode::default(),
+        };
         let components: [Box<dyn Component>; Mode::COUNT] = [
             Box::<Home>::default(),
             Box::<Memory>::default(),
             Box::<Help>::default(),
+            Box::<Profiler>::default(),
+            Box::<Checkpoints>::default(),
+            Box::new(Verifier::new(verification)),
+            Box::<InputEditor>::default(),
         ];
 
-        let vm_state = TritonVMState::new(&args)?;
+        let session_recorder = args.record.as_deref().map(SessionRecorder::new);
+        let replay = args
+            .replay
+            .as_deref()
+            .map(|path| Replay::load(path, args.replay_stepwise));
+        let file_watcher = args
+            .input_args
+            .as_ref()
+            .filter(|_| args.watch)
+            .map(FileWatcher::new);
 
         Ok(Self {
             args,
@@ -64,9 +100,41 @@ impl TritonTUI {
             recent_key_events_reset_delay: 0,
             recent_key_events: vec![],
             vm_state,
+            session_recorder: session_recorder.transpose()?,
+            replay: replay.transpose()?,
+            file_watcher,
         })
     }
 
+    /// Set the catchpoints of the config like those set on the command line. Warns about those
+    /// that do not fit the program, for example because they refer to a label that does not
+    /// exist.
+    fn set_catchpoints_from_config(vm_state: &mut TritonVMState, config: &Config) -> Result<()> {
+        let mut warnings = vec![];
+        for catchpoint in &config.catchpoints {
+            vm_state.update(Action::SetCatchpoint(catchpoint.clone()))?;
+            if let Some(warning) = vm_state.warning.take() {
+                warnings.push(format!("catchpoint “{catchpoint}” from config: {warning}"));
+            }
+        }
+        if !warnings.is_empty() {
+            vm_state.warning = Some(anyhow!("{}", warnings.join("; ")));
+        }
+        Ok(())
+    }
+
+    /// Verify the proof given on the command line, if any, against its claim. Also checks the
+    /// claim against the loaded program, unless there is none.
+    fn verification(args: &TuiArgs, vm_state: &TritonVMState) -> Result<Option<Verification>> {
+        let (Some(proof_path), Some(claim_path)) = (&args.proof, &args.claim) else {
+            return Ok(None);
+        };
+        let has_program = args.input_args.is_some() || args.initial_state.is_some();
+        let program = has_program.then_some(&vm_state.vm_state.program);
+        let verification = Verification::load(proof_path, claim_path, program)?;
+        Ok(Some(verification))
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         let (action_tx, mut action_rx) = mpsc::unbounded_channel();
         self.tui.enter()?;
@@ -75,13 +143,35 @@ impl TritonTUI {
         for component in &mut self.components {
             component.register_action_handler(action_tx.clone())?;
         }
+        self.run_script()?;
 
         while !self.should_quit {
             self.handle_events_and_actions(&action_tx, &mut action_rx)
                 .await?;
         }
 
-        self.tui.exit()
+        self.tui.exit()?;
+        if let Some(path) = &self.args.profile {
+            self.vm_state.export_profile(path)?;
+        }
+        Ok(())
+    }
+
+    /// Run the script given on the command line, if any. Failed expectations are shown as a
+    /// warning.
+    fn run_script(&mut self) -> Result<()> {
+        let Some(ref path) = self.args.script else {
+            return Ok(());
+        };
+        let failed_expectations = Script::load(path)?.run(&mut self.vm_state)?;
+        let Some(first_failure) = failed_expectations.first() else {
+            return Ok(());
+        };
+        let num_failures = failed_expectations.len();
+        self.vm_state.warning = Some(anyhow!(
+            "{num_failures} expectation(s) of script failed, first: {first_failure}"
+        ));
+        Ok(())
     }
 
     async fn handle_events_and_actions(
@@ -147,9 +237,15 @@ impl TritonTUI {
         action_tx: &UnboundedSender<Action>,
         action_rx: &mut UnboundedReceiver<Action>,
     ) -> Result<()> {
-        while let Ok(action) = action_rx.try_recv() {
+        while let Some(action) = self.next_action(action_rx) {
+            if let Some(ref mut recorder) = self.session_recorder {
+                recorder.record(&action)?;
+            }
             match action {
-                Action::Tick => self.maybe_clear_recent_key_events(),
+                Action::Tick => {
+                    self.maybe_clear_recent_key_events();
+                    self.maybe_request_reload(action_tx)?;
+                }
                 Action::Render => self.render()?,
                 Action::Resize(w, h) => {
                     self.tui.resize(Rect::new(0, 0, w, h))?;
@@ -160,9 +256,11 @@ impl TritonTUI {
                     self.render()?;
                 }
                 Action::Reset => self.reset_state(action_tx)?,
+                Action::Reload => self.reload_state(action_tx)?,
                 Action::Suspend => self.should_suspend = true,
                 Action::Resume => self.should_suspend = false,
                 Action::Quit => self.should_quit = true,
+                Action::ReplayNext => self.replay_next_action(action_tx)?,
                 _ => {}
             }
 
@@ -176,6 +274,28 @@ impl TritonTUI {
         Ok(())
     }
 
+    /// The next pending action. Once all pending actions are handled, the next replayed
+    /// action, if a session is replayed at full speed.
+    fn next_action(&mut self, action_rx: &mut UnboundedReceiver<Action>) -> Option<Action> {
+        if let Ok(action) = action_rx.try_recv() {
+            return Some(action);
+        }
+        let replay = self.replay.as_mut().filter(|replay| !replay.stepwise)?;
+        replay.next_action()
+    }
+
+    fn replay_next_action(&mut self, action_tx: &UnboundedSender<Action>) -> Result<()> {
+        let Some(ref mut replay) = self.replay else {
+            self.vm_state.warning = Some(anyhow!("no session is being replayed"));
+            return Ok(());
+        };
+        match replay.next_action() {
+            Some(action) => action_tx.send(action)?,
+            None => self.vm_state.warning = Some(anyhow!("replayed all actions of the session")),
+        }
+        Ok(())
+    }
+
     fn maybe_clear_recent_key_events(&mut self) {
         if self.recent_key_events_reset_delay > 0 {
             self.recent_key_events_reset_delay -= 1;
@@ -185,15 +305,75 @@ impl TritonTUI {
     }
 
     fn reset_state(&mut self, action_tx: &UnboundedSender<Action>) -> Result<()> {
-        let vm_state = match TritonVMState::new(&self.args) {
+        let Some(vm_state) = self.fresh_vm_state() else {
+            return Ok(());
+        };
+        self.vm_state = vm_state;
+        self.vm_state.register_action_handler(action_tx.clone())?;
+        self.render()?;
+        Ok(())
+    }
+
+    /// A new state built from the files given on the command line, keeping breakpoints,
+    /// watchpoints, and catchpoints as far as they fit the loaded program. Warns about those
+    /// that do not. If the files cannot be loaded, sets a warning instead.
+    fn fresh_vm_state(&mut self) -> Option<TritonVMState> {
+        let mut vm_state = match TritonVMState::new(&self.args) {
             Ok(vm_state) => vm_state,
             Err(report) => {
                 self.vm_state.warning = Some(report);
-                return Ok(());
+                return None;
             }
         };
+        let dropped = vm_state.adopt_breakpoints_of(&self.vm_state);
+        if !dropped.is_empty() {
+            let dropped = dropped.join(", ");
+            vm_state.warning = Some(anyhow!("dropped {dropped}: not in the loaded program"));
+        }
+        Some(vm_state)
+    }
+
+    fn maybe_request_reload(&mut self, action_tx: &UnboundedSender<Action>) -> Result<()> {
+        let Some(ref mut file_watcher) = self.file_watcher else {
+            return Ok(());
+        };
+        if file_watcher.files_changed() {
+            action_tx.send(Action::Reload)?;
+        }
+        Ok(())
+    }
+
+    /// Reload the program and its inputs, then run to where execution was before reloading. If
+    /// execution stops earlier, for example because of an error, stays there and says so.
+    fn reload_state(&mut self, action_tx: &UnboundedSender<Action>) -> Result<()> {
+        let position = ExecutionPosition::of(&self.vm_state.vm_state);
+        let Some(mut vm_state) = self.fresh_vm_state() else {
+            return Ok(());
+        };
+        let is_same_program = vm_state.vm_state.program == self.vm_state.vm_state.program;
+        vm_state.register_action_handler(action_tx.clone())?;
         self.vm_state = vm_state;
-        self.vm_state.register_action_handler(action_tx.clone())?;
+        for component in &mut self.components {
+            if let Some(action) = component.update(Action::Reset)? {
+                action_tx.send(action)?;
+            }
+        }
+
+        match self.vm_state.replay_to(&position, is_same_program) {
+            None => self.vm_state.info = Some(format!("reloaded, back at {position}")),
+            Some(reason) => {
+                let cycle = self.vm_state.vm_state.cycle_count;
+                let divergence = anyhow!(
+                    "reloaded program diverges at cycle {cycle}, before reaching {position}: \
+                    {reason}"
+                );
+                let warning = match self.vm_state.warning.take() {
+                    Some(dropped) => anyhow!("{divergence}; {dropped}"),
+                    None => divergence,
+                };
+                self.vm_state.warning = Some(warning);
+            }
+        }
         self.render()?;
         Ok(())
     }
@@ -241,8 +421,13 @@ impl TritonTUI {
         };
         self.recent_key_events.push(key);
         self.recent_key_events_reset_delay = RECENT_KEY_EVENTS_RESET_DELAY;
-        if let Some(action) = keymap.get(&self.recent_key_events) {
-            action_tx.send(action.clone())?;
+        let (repeat_count, key_events) = split_repeat_count(&self.recent_key_events);
+        if let Some(action) = keymap.get(key_events) {
+            let action = match (action.clone(), repeat_count) {
+                (Action::Execute(execute), Some(count)) => Action::Execute(execute.repeated(count)),
+                (action, _) => action,
+            };
+            action_tx.send(action)?;
             self.recent_key_events.clear();
         }
         if key.code == KeyCode::Esc && key.kind != KeyEventKind::Release {
@@ -1,3 +1,9 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Instant;
+
 use color_eyre::Report;
 use color_eyre::eyre::Result;
 use color_eyre::eyre::anyhow;
@@ -16,9 +22,27 @@ use triton_vm::prelude::*;
 use crate::action::*;
 use crate::args::InputArgs;
 use crate::args::TuiArgs;
+use crate::args::get_data_dir;
+use crate::catchpoint::Catchpoint;
+use crate::command::Location;
 use crate::components::Component;
+use crate::condition::Condition;
+use crate::history::Checkpoint;
+use crate::history::History;
+use crate::history::NamedCheckpoint;
+use crate::profile::Profile;
+use crate::prover;
+use crate::prover::ProofReport;
+use crate::prover::ProverStatus;
+use crate::prover::ProvingJob;
+use crate::prover::ProvingPhase;
+use crate::reload::ExecutionPosition;
 use crate::shadow_memory::ShadowMemory;
 use crate::shadow_memory::TopOfStack;
+use crate::table_heights::TableHeights;
+use crate::watchpoint::AddressRange;
+use crate::watchpoint::Watchpoint;
+use crate::watchpoint::WatchpointHit;
 
 #[derive(Debug)]
 pub(crate) struct TritonVMState {
@@ -27,8 +51,76 @@ pub(crate) struct TritonVMState {
     pub vm_state: VMState,
 
     pub type_hints: ShadowMemory,
-    pub undo_stack: Vec<UndoInformation>,
 
+    /// The cycles spent per function and call stack since the beginning of execution.
+    pub profile: Profile,
+
+    /// The heights of the tables of the Algebraic Execution Trace since the beginning of
+    /// execution.
+    pub table_heights: TableHeights,
+
+    /// The padded height after executing the next instruction. Is `None` if the next
+    /// instruction cannot be executed.
+    pub padded_height_after_next_instruction: Option<usize>,
+
+    /// The cycles at which the [`Execute`] actions that can be undone were started. Undoing
+    /// travels back (or forth) to the most recent one, using the [`History`].
+    pub undo_stack: Vec<u32>,
+
+    /// The cycles at which undone [`Execute`] actions were undone. Cleared by any new
+    /// [`Execute`] action.
+    pub redo_stack: Vec<u32>,
+
+    /// Checkpoints allowing to travel to earlier cycles.
+    pub history: History,
+
+    /// The highest cycle count reached since the last reset.
+    pub furthest_cycle: u32,
+
+    /// Checkpoints saved by the user, in order of creation.
+    pub named_checkpoints: Vec<NamedCheckpoint>,
+
+    /// The cycles at which execution stopped because of a breakpoint.
+    pub breakpoint_hit_cycles: BTreeSet<u32>,
+
+    /// The RAM address most recently accessed by any instruction. Mirrors the corresponding
+    /// field of the [`Memory`][memory] component, allowing to restore it when travelling back.
+    ///
+    /// [memory]: crate::components::memory::Memory
+    pub most_recent_ram_address: BFieldElement,
+
+    /// Breakpoints set at runtime, in addition to the ones in the program's source code.
+    /// Identified by their instruction address. Conditional breakpoints only stop execution if
+    /// their condition holds.
+    pub breakpoints: BTreeMap<usize, Option<Condition>>,
+
+    pub watchpoints: Vec<Watchpoint>,
+    pub catchpoints: Vec<Catchpoint>,
+
+    /// The RAM accesses that triggered a watchpoint during the most recently executed
+    /// instruction. Execution does not advance while this is non-empty.
+    pub watchpoint_hits: Vec<WatchpointHit>,
+
+    /// The catchpoint that triggered during the most recently executed instruction, if any.
+    /// Execution does not advance while this is set.
+    pub catchpoint_hit: Option<Catchpoint>,
+
+    /// Whether to ask for missing input instead of failing to execute `read_io` or `divine`.
+    pub interactive_input: bool,
+
+    /// The input the next instruction is missing, if execution paused to ask for it. Execution
+    /// does not advance while this is set.
+    pub input_request: Option<InputRequest>,
+
+    /// The [`Execute`] action interrupted by the most recent input request, and the cycle it
+    /// started at. Once the input is entered, the action is re-issued from that cycle, as if
+    /// the input had been there all along.
+    pub execute_after_input: Option<(u32, Execute)>,
+
+    pub prover_status: ProverStatus,
+
+    /// A message about something that went as planned, like writing a file.
+    pub info: Option<String>,
     pub warning: Option<Report>,
     pub error: Option<InstructionError>,
 
@@ -36,60 +128,124 @@ pub(crate) struct TritonVMState {
     pub interrupt_cycle: u32,
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct UndoInformation {
-    vm_state: VMState,
-    type_hints: ShadowMemory,
-}
-
 impl TritonVMState {
+    /// The key under which [`Self::dump_state`] stores the type hints next to the VM state.
+    const TYPE_HINTS_KEY: &'static str = "type_hints";
+
+    /// The file name of the public input written by [`Self::export_input`].
+    const PUBLIC_INPUT_FILE_NAME: &'static str = "public_input.txt";
+
+    /// The file name of the non-determinism written by [`Self::export_input`].
+    const NON_DETERMINISM_FILE_NAME: &'static str = "non_determinism.json";
+
     pub fn new(args: &TuiArgs) -> Result<Self> {
-        let vm_state = if let Some(ref input_args) = args.input_args {
+        let (vm_state, type_hints) = if let Some(ref input_args) = args.input_args {
             let program = Self::program_from_args(input_args)?;
-            Self::vm_state_with_specified_input(input_args, program)?
+            let vm_state = Self::vm_state_with_specified_input(input_args, program)?;
+            (
+                vm_state,
+                Some(ShadowMemory::new_for_default_initial_state()),
+            )
+        } else if args.initial_state.is_none() && args.proof.is_some() {
+            // Verifying a proof does not require a program. There is nothing to step through.
+            let vm_state = VMState::new(Program::new(&[]), [].into(), [].into());
+            (
+                vm_state,
+                Some(ShadowMemory::new_for_default_initial_state()),
+            )
         } else {
             Self::vm_state_from_initial_state(args)?
         };
+        Ok(Self::from_vm_state(vm_state, type_hints, args))
+    }
 
-        let type_hints = if args.initial_state.is_some() {
-            ShadowMemory::new_for_initial_state(&vm_state)
-        } else {
-            ShadowMemory::new_for_default_initial_state()
-        };
-
+    /// Start debugging at the given VM state. Everything derived from the VM state, like the
+    /// profile and the history, starts out fresh. Without explicit type hints, none of the
+    /// stack elements are annotated.
+    pub fn from_vm_state(
+        vm_state: VMState,
+        type_hints: Option<ShadowMemory>,
+        args: &TuiArgs,
+    ) -> Self {
+        let type_hints =
+            type_hints.unwrap_or_else(|| ShadowMemory::new_for_initial_state(&vm_state));
+        let profile = Profile::new(&vm_state);
+        let table_heights = TableHeights::new(&vm_state.program);
+        let initial_checkpoint = Checkpoint::new(
+            vm_state.clone(),
+            type_hints.clone(),
+            profile.clone(),
+            table_heights.clone(),
+            bfe!(0),
+        );
+        let history_memory_limit = args.history_memory_limit.saturating_mul(1 << 20);
         let mut state = Self {
             action_tx: None,
             vm_state,
             type_hints,
+            profile,
+            table_heights,
+            padded_height_after_next_instruction: None,
             undo_stack: vec![],
+            redo_stack: vec![],
+            history: History::new(initial_checkpoint, history_memory_limit),
+            furthest_cycle: 0,
+            named_checkpoints: vec![],
+            breakpoint_hit_cycles: BTreeSet::new(),
+            most_recent_ram_address: bfe!(0),
+            breakpoints: BTreeMap::new(),
+            watchpoints: vec![],
+            catchpoints: vec![],
+            watchpoint_hits: vec![],
+            catchpoint_hit: None,
+            interactive_input: args.interactive_input,
+            input_request: None,
+            execute_after_input: None,
+            prover_status: ProverStatus::Idle,
+            info: None,
             warning: None,
             error: None,
             num_cycles_since_user_action: 0,
             interrupt_cycle: args.interrupt_cycle,
         };
         state.apply_type_hints();
-        Ok(state)
+        state.anticipate_padded_height();
+        state
     }
 
-    fn program_from_args(args: &InputArgs) -> Result<Program> {
+    pub fn program_from_args(args: &InputArgs) -> Result<Program> {
         let source_code = fs::read_to_string(&args.program)?;
         let program = Program::from_code(&source_code)
             .map_err(|err| anyhow!("program parsing error: {err}"))?;
         Ok(program)
     }
 
-    fn vm_state_from_initial_state(args: &TuiArgs) -> Result<VMState> {
+    /// Load the initial state and, if the file contains them, the type hints. See
+    /// [`Self::dump_state`].
+    fn vm_state_from_initial_state(args: &TuiArgs) -> Result<(VMState, Option<ShadowMemory>)> {
         let Some(ref initial_state_path) = args.initial_state else {
             let error_desc = "path to initial state must exist";
             error!(error_desc);
             bail!(error_desc);
         };
         let file = fs::File::open(initial_state_path)?;
+        let mut initial_state: serde_json::Value = serde_json::from_reader(file)?;
 
-        Ok(serde_json::from_reader(file)?)
+        let type_hints = initial_state
+            .as_object_mut()
+            .and_then(|initial_state| initial_state.remove(Self::TYPE_HINTS_KEY))
+            .map(serde_json::from_value::<ShadowMemory>)
+            .transpose()?;
+        let vm_state: VMState = serde_json::from_value(initial_state)?;
+        if let Some(ref type_hints) = type_hints
+            && type_hints.stack.len() != vm_state.op_stack.len()
+        {
+            bail!("type hints of initial state do not match its op stack");
+        }
+        Ok((vm_state, type_hints))
     }
 
-    fn vm_state_with_specified_input(args: &InputArgs, program: Program) -> Result<VMState> {
+    pub fn vm_state_with_specified_input(args: &InputArgs, program: Program) -> Result<VMState> {
         let public_input = Self::public_input_from_args(args)?;
         let non_determinism = Self::non_determinism_from_args(args)?;
         let vm_state = VMState::new(program, public_input, non_determinism);
@@ -101,13 +257,18 @@ impl TritonVMState {
             return Ok(PublicInput::default());
         };
         let file_content = fs::read_to_string(input_path)?;
-        let string_tokens = file_content.split_whitespace();
+        let elements = Self::parse_public_input(&file_content)?;
+        Ok(PublicInput::new(elements))
+    }
+
+    /// Parse whitespace-separated elements, as found in files of public input.
+    pub fn parse_public_input(text: &str) -> Result<Vec<BFieldElement>> {
         let mut elements = vec![];
-        for string_token in string_tokens {
+        for string_token in text.split_whitespace() {
             let element = string_token.parse::<u64>()?;
             elements.push(element.into());
         }
-        Ok(PublicInput::new(elements))
+        Ok(elements)
     }
 
     fn non_determinism_from_args(input_args: &InputArgs) -> Result<NonDeterminism> {
@@ -128,16 +289,201 @@ impl TritonVMState {
     }
 
     fn vm_is_stopped(&self) -> bool {
-        self.vm_state.halting || self.error.is_some() || self.interrupted()
+        self.vm_state.halting
+            || self.error.is_some()
+            || !self.watchpoint_hits.is_empty()
+            || self.catchpoint_hit.is_some()
+            || self.input_request.is_some()
+            || self.interrupted()
     }
 
-    fn vm_is_running(&self) -> bool {
+    pub fn vm_is_running(&self) -> bool {
         !self.vm_is_stopped()
     }
 
-    fn at_breakpoint(&self) -> bool {
-        let ip = self.vm_state.instruction_pointer as u64;
-        self.vm_state.program.is_breakpoint(ip)
+    /// Whether execution should stop at the current instruction. A breakpoint with a condition
+    /// that cannot be evaluated stops execution and sets a warning.
+    fn at_breakpoint(&mut self) -> bool {
+        let ip = self.vm_state.instruction_pointer;
+        if self.vm_state.program.is_breakpoint(ip as u64) {
+            return true;
+        }
+        let Some(maybe_condition) = self.breakpoints.get(&ip) else {
+            return false;
+        };
+        let Some(condition) = maybe_condition else {
+            return true;
+        };
+        let evaluation = condition
+            .evaluate(self)
+            .map_err(|err| anyhow!("cannot evaluate breakpoint condition “{condition}”: {err}"));
+        match evaluation {
+            Ok(holds) => holds,
+            Err(report) => {
+                self.warning = Some(report);
+                true
+            }
+        }
+    }
+
+    /// Set a runtime breakpoint at the given address, or remove it if it already exists.
+    fn toggle_breakpoint(&mut self, address: usize) {
+        if self.vm_state.program.is_breakpoint(address as u64) {
+            self.warning = Some(anyhow!(
+                "breakpoint at address {address} is part of the program's source code"
+            ));
+            return;
+        }
+        if self.breakpoints.remove(&address).is_none() {
+            self.breakpoints.insert(address, None);
+        }
+    }
+
+    fn set_breakpoint(&mut self, location: &Location, condition: Option<Condition>) {
+        let address = match location.resolve(&self.vm_state.program) {
+            Ok(address) => address,
+            Err(report) => {
+                self.warning = Some(report);
+                return;
+            }
+        };
+        if self.vm_state.program.is_breakpoint(address as u64) {
+            self.warning = Some(anyhow!(
+                "breakpoint at {location} is part of the program's source code"
+            ));
+            return;
+        }
+        self.breakpoints.insert(address, condition);
+    }
+
+    fn delete_breakpoint(&mut self, location: &Location) {
+        let address = match location.resolve(&self.vm_state.program) {
+            Ok(address) => address,
+            Err(report) => {
+                self.warning = Some(report);
+                return;
+            }
+        };
+        if self.breakpoints.remove(&address).is_none() {
+            self.warning = Some(anyhow!("no runtime breakpoint at {location}"));
+        }
+    }
+
+    fn set_watchpoint(&mut self, watchpoint: Watchpoint) {
+        if !self.watchpoints.contains(&watchpoint) {
+            self.watchpoints.push(watchpoint);
+        }
+    }
+
+    /// Remove all watchpoints overlapping the given address range.
+    fn delete_watchpoints(&mut self, addresses: AddressRange) {
+        let num_watchpoints = self.watchpoints.len();
+        self.watchpoints
+            .retain(|watchpoint| !watchpoint.addresses.overlaps(&addresses));
+        if self.watchpoints.len() == num_watchpoints {
+            self.warning = Some(anyhow!("no watchpoint at address {addresses}"));
+        }
+    }
+
+    /// The RAM accesses of the upcoming instruction that trigger a watchpoint, together with the
+    /// accessed RAM cells' current values.
+    fn watched_ram_accesses(&self, top_of_stack: &TopOfStack) -> Vec<(RamAccess, BFieldElement)> {
+        if self.watchpoints.is_empty() {
+            return vec![];
+        }
+        let Ok(instruction) = self.vm_state.current_instruction() else {
+            return vec![];
+        };
+        RamAccess::of(instruction, top_of_stack)
+            .into_iter()
+            .filter(|&access| self.watchpoints.iter().any(|w| w.is_triggered_by(access)))
+            .map(|access| (access, self.ram_value(access.address)))
+            .collect()
+    }
+
+    fn ram_value(&self, address: BFieldElement) -> BFieldElement {
+        self.vm_state.ram.get(&address).copied().unwrap_or_default()
+    }
+
+    fn set_catchpoint(&mut self, catchpoint: Catchpoint) {
+        if let Catchpoint::Call(ref label) = catchpoint {
+            let location = Location::Label(label.clone());
+            if let Err(report) = location.resolve(&self.vm_state.program) {
+                self.warning = Some(report);
+                return;
+            }
+        }
+        if !self.catchpoints.contains(&catchpoint) {
+            self.catchpoints.push(catchpoint);
+        }
+    }
+
+    /// Take over the breakpoints, watchpoints, and catchpoints of the given state, which may have
+    /// run a different version of the program. Breakpoints move along with the label they are
+    /// at, if any. Breakpoints and catchpoints that do not fit this program, for example because
+    /// their label no longer exists, are dropped. Returns descriptions of the dropped ones.
+    pub fn adopt_breakpoints_of(&mut self, previous: &Self) -> Vec<String> {
+        let warning = self.warning.take();
+        let mut dropped = vec![];
+        for (location, condition) in previous.breakpoint_locations() {
+            self.set_breakpoint(&location, condition);
+            if self.warning.take().is_some() {
+                dropped.push(format!("breakpoint at {location}"));
+            }
+        }
+        for catchpoint in &previous.catchpoints {
+            self.set_catchpoint(catchpoint.clone());
+            if self.warning.take().is_some() {
+                dropped.push(format!("catchpoint “{catchpoint}”"));
+            }
+        }
+        self.watchpoints.clone_from(&previous.watchpoints);
+        self.warning = warning;
+        dropped
+    }
+
+    /// The location of every breakpoint, using the label at its address if there is one.
+    fn breakpoint_locations(&self) -> Vec<(Location, Option<Condition>)> {
+        let mut labels = BTreeMap::new();
+        let mut address = 0;
+        for instruction in self.vm_state.program.labelled_instructions() {
+            match instruction {
+                LabelledInstruction::Label(label) => _ = labels.entry(address).or_insert(label),
+                LabelledInstruction::Instruction(instruction) => address += instruction.size(),
+                _ => (),
+            }
+        }
+        self.breakpoints
+            .iter()
+            .map(|(address, condition)| {
+                let location = match labels.get(address) {
+                    Some(label) => Location::Label(label.clone()),
+                    None => Location::Address(*address),
+                };
+                (location, condition.clone())
+            })
+            .collect()
+    }
+
+    fn delete_catchpoint(&mut self, catchpoint: &Catchpoint) {
+        let num_catchpoints = self.catchpoints.len();
+        self.catchpoints.retain(|c| c != catchpoint);
+        if self.catchpoints.len() == num_catchpoints {
+            self.warning = Some(anyhow!("no catchpoint “{catchpoint}”"));
+        }
+    }
+
+    /// The first catchpoint triggered by the just-executed instruction or by the upcoming one.
+    fn triggered_catchpoint(&self, executed_instruction: Instruction) -> Option<Catchpoint> {
+        let program = &self.vm_state.program;
+        let next_instruction = self.vm_state.current_instruction().ok();
+        self.catchpoints
+            .iter()
+            .find(|catchpoint| {
+                catchpoint.is_triggered_after(executed_instruction, program)
+                    || next_instruction.is_some_and(|next| catchpoint.is_triggered_before(next))
+            })
+            .cloned()
     }
 
     fn apply_type_hints(&mut self) {
@@ -153,48 +499,596 @@ impl TritonVMState {
 
     fn execute(&mut self, execute: &Execute) {
         self.num_cycles_since_user_action = 0;
-        self.record_undo_information();
-        match execute {
-            Execute::Continue => self.continue_execution(),
-            Execute::Step => self.step(),
-            Execute::Next => self.next(),
-            Execute::Finish => self.finish(),
+        self.watchpoint_hits.clear();
+        self.catchpoint_hit = None;
+        self.input_request = None;
+        self.record_undo_information(execute);
+        self.run(execute);
+    }
+
+    /// Execute the given way. If execution pauses for missing input, remembers how to continue
+    /// once the input is entered.
+    fn run(&mut self, execute: &Execute) {
+        let start_cycle = self.vm_state.cycle_count;
+        match *execute {
+            Execute::Continue(count) => self.repeat(count, Self::continue_execution),
+            Execute::Step(count) => self.repeat(count, Self::step),
+            Execute::Next(count) => self.repeat(count, Self::next),
+            Execute::Finish(count) => self.repeat(count, Self::finish),
+            Execute::ReverseContinue(count) => self.repeat(count, Self::reverse_continue),
+            Execute::ReverseStep(count) => self.reverse_step(count),
+            Execute::ReverseNext(count) => self.repeat(count, Self::reverse_next),
+            Execute::ReverseFinish(count) => self.repeat(count, Self::reverse_finish),
+            Execute::RunTo(ref location) => self.run_to(location),
+            Execute::GotoCycle(cycle) => self.goto_cycle(cycle),
+        }
+        if let Some(input_request) = self.input_request {
+            self.execute_after_input = Some((start_cycle, execute.clone()));
+            self.send_input_request(input_request);
+        }
+    }
+
+    /// Execute the given way the given number of times. Stops early if Triton VM stops or
+    /// anything warrants a warning.
+    fn repeat(&mut self, count: u32, mut execute: impl FnMut(&mut Self)) {
+        for _ in 0..count {
+            execute(self);
+            if self.vm_is_stopped() || self.warning.is_some() {
+                return;
+            }
         }
     }
 
     /// Handle [`Execute::Continue`].
     fn continue_execution(&mut self) {
         self.step();
-        while self.vm_is_running() && !self.at_breakpoint() {
+        while self.vm_is_running() {
+            if self.at_breakpoint() {
+                self.record_breakpoint_hit();
+                return;
+            }
             self.step();
         }
     }
 
+    fn record_breakpoint_hit(&mut self) {
+        self.breakpoint_hit_cycles.insert(self.vm_state.cycle_count);
+    }
+
     /// Handle [`Execute::Step`].
     fn step(&mut self) {
         if self.vm_is_stopped() {
             return;
         }
+        if self.interactive_input {
+            self.input_request = self.missing_input();
+            if self.input_request.is_some() {
+                return;
+            }
+        }
 
-        let instruction = self.vm_state.current_instruction().ok();
         let old_top_of_stack = self.top_of_stack();
-        if let Err(err) = self.vm_state.step() {
-            warn!("Error stepping: {err}");
-            self.error = Some(err);
-            return;
-        }
-        self.warning = None;
+        let watched_ram_accesses = self.watched_ram_accesses(&old_top_of_stack);
+        let executed_instruction = match self.execute_instruction() {
+            Ok(executed_instruction) => executed_instruction,
+            Err(err) => {
+                warn!("Error stepping: {err}");
+                self.error = Some(err);
+                return;
+            }
+        };
+        self.watchpoint_hits = watched_ram_accesses
+            .into_iter()
+            .map(|(access, old_value)| WatchpointHit {
+                access,
+                old_value,
+                new_value: self.ram_value(access.address),
+            })
+            .collect();
         self.num_cycles_since_user_action += 1;
         self.maybe_inform_about_interrupt();
 
-        let instruction = instruction.expect("instruction should exist after successful `step`");
+        if !self.catchpoints.is_empty() && !self.vm_state.halting {
+            let instruction = executed_instruction.instruction;
+            self.catchpoint_hit = self.triggered_catchpoint(instruction);
+        }
+        self.send_executed_transaction(executed_instruction);
+    }
+
+    /// Execute the current instruction and keep the type hints in sync, recording a checkpoint
+    /// if one is due. Unlike [`Self::step`], neither informs other components nor checks for
+    /// watchpoints, catchpoints, or interrupts.
+    fn execute_instruction(&mut self) -> Result<ExecutedInstruction, InstructionError> {
+        if self.history.checkpoint_is_due(self.vm_state.cycle_count) {
+            self.history.record(self.checkpoint());
+        }
+
+        let instruction = self.vm_state.current_instruction()?;
+        let old_top_of_stack = self.top_of_stack();
+        let address = self.vm_state.instruction_pointer;
+        let co_processor_calls = self.vm_state.step()?;
+        if let Some(edited) = self.history.edited_checkpoint_at(self.vm_state.cycle_count) {
+            self.vm_state.public_input = edited.vm_state.public_input.clone();
+        }
+        self.profile.record_cycle(address, &self.vm_state);
+        self.table_heights.record(co_processor_calls);
         let new_top_of_stack = self.top_of_stack();
         let executed_instruction =
             ExecutedInstruction::new(instruction, old_top_of_stack, new_top_of_stack);
+        self.furthest_cycle = self.furthest_cycle.max(self.vm_state.cycle_count);
 
-        self.send_executed_transaction(executed_instruction);
+        if let Some(access) = executed_instruction.ram_accesses().last() {
+            self.most_recent_ram_address = access.address;
+        }
+        self.info = None;
+        self.warning = None;
         self.type_hints.mimic_instruction(executed_instruction);
         self.apply_type_hints();
+        Ok(executed_instruction)
+    }
+
+    fn checkpoint(&self) -> Checkpoint {
+        let vm_state = self.vm_state.clone();
+        let type_hints = self.type_hints.clone();
+        let profile = self.profile.clone();
+        let table_heights = self.table_heights.clone();
+        let ram_address = self.most_recent_ram_address;
+        Checkpoint::new(vm_state, type_hints, profile, table_heights, ram_address)
+    }
+
+    fn restore(&mut self, checkpoint: Checkpoint) {
+        let mut vm_state = checkpoint.vm_state;
+        std::mem::swap(&mut vm_state.program, &mut self.vm_state.program);
+        self.vm_state = vm_state;
+        self.type_hints = checkpoint.type_hints;
+        self.profile = checkpoint.profile;
+        self.table_heights = checkpoint.table_heights;
+        self.most_recent_ram_address = checkpoint.most_recent_ram_address;
+        self.clear_reasons_for_stopping();
+    }
+
+    fn clear_reasons_for_stopping(&mut self) {
+        self.info = None;
+        self.warning = None;
+        self.error = None;
+        self.watchpoint_hits.clear();
+        self.catchpoint_hit = None;
+        self.input_request = None;
+    }
+
+    /// Bring Triton VM into the state it was in, or will be in, at the given cycle. Re-executes
+    /// from the most recent checkpoint preceding the given cycle, unless the current state is
+    /// closer.
+    fn travel_to_cycle(&mut self, cycle: u32) {
+        let current_cycle = self.vm_state.cycle_count;
+        let Some(checkpoint) = self.history.checkpoint_at_or_before(cycle) else {
+            self.warning = Some(anyhow!("no history available for cycle {cycle}"));
+            return;
+        };
+        if cycle < current_cycle || checkpoint.cycle() > current_cycle {
+            self.restore(checkpoint.clone());
+        } else {
+            self.clear_reasons_for_stopping();
+        }
+        while self.vm_state.cycle_count < cycle && !self.vm_state.halting {
+            // Cycles never reached before might never end, like any other execution.
+            if self.vm_state.cycle_count >= self.furthest_cycle {
+                if self.interrupted() {
+                    self.maybe_inform_about_interrupt();
+                    break;
+                }
+                self.num_cycles_since_user_action += 1;
+            }
+            if let Err(err) = self.execute_instruction() {
+                self.error = Some(err);
+                break;
+            }
+        }
+
+        self.send_rewound();
+    }
+
+    /// The input the current instruction needs but that is not available, if any.
+    fn missing_input(&self) -> Option<InputRequest> {
+        let instruction = self.vm_state.current_instruction().ok()?;
+        InputRequest::for_instruction(
+            instruction,
+            self.vm_state.public_input.len(),
+            self.vm_state.secret_individual_tokens.len(),
+        )
+    }
+
+    fn send_input_request(&self, input_request: InputRequest) {
+        let Some(ref action_tx) = self.action_tx else {
+            error!("action_tx must exist");
+            return;
+        };
+        let _ = action_tx.send(Action::RequestInput(input_request));
+    }
+
+    /// Handle [`Action::EnterInput`]. The entered elements are appended to the input of every
+    /// state, be it current, past, or saved by name, as if they had been part of the input from
+    /// the start. Once enough input is entered, the interrupted execution is re-issued from where
+    /// it started, which keeps repeat counts and call depths of `next` and `finish` intact.
+    fn enter_input(&mut self, elements: &[BFieldElement]) {
+        let Some(input_request) = self.input_request.take() else {
+            self.warning = Some(anyhow!("no input was requested"));
+            return;
+        };
+        let (public_input, secret_input) = if input_request.is_for_public_input() {
+            (elements, [].as_slice())
+        } else {
+            ([].as_slice(), elements)
+        };
+        self.vm_state.public_input.extend(public_input);
+        self.vm_state.secret_individual_tokens.extend(secret_input);
+        self.history.append_input(public_input, secret_input);
+        for named_checkpoint in &mut self.named_checkpoints {
+            let checkpoint = &mut named_checkpoint.checkpoint;
+            checkpoint.append_input(public_input, secret_input);
+        }
+
+        let num_missing = input_request.num_missing.saturating_sub(elements.len());
+        if num_missing > 0 {
+            let input_request = InputRequest {
+                num_missing,
+                ..input_request
+            };
+            self.input_request = Some(input_request);
+            self.send_input_request(input_request);
+            return;
+        }
+        if let Some((start_cycle, execute)) = self.execute_after_input.take() {
+            self.travel_to_cycle(start_cycle);
+            self.run(&execute);
+        }
+    }
+
+    /// Write the input of the entire session, as given on startup plus everything entered since,
+    /// to the given directory. The files can be passed back in with `--input` and
+    /// `--non-determinism`. Returns the paths of both files, in that order.
+    ///
+    /// Edits made with the public input editor are not included: they take effect in the middle
+    /// of execution, which no input file can express.
+    fn export_input(&self, directory: &Path) -> Result<(PathBuf, PathBuf)> {
+        let initial_state = &self.history.initial_checkpoint().vm_state;
+        let public_input = initial_state.public_input.iter().join(" ");
+        let non_determinism = NonDeterminism {
+            individual_tokens: initial_state.secret_individual_tokens.clone().into(),
+            digests: initial_state.secret_digests.clone().into(),
+            ram: initial_state.ram.clone(),
+        };
+
+        fs::create_dir_all(directory)?;
+        let public_input_path = directory.join(Self::PUBLIC_INPUT_FILE_NAME);
+        let non_determinism_path = directory.join(Self::NON_DETERMINISM_FILE_NAME);
+        fs::write(&public_input_path, format!("{public_input}\n"))?;
+        fs::write(
+            &non_determinism_path,
+            serde_json::to_string(&non_determinism)?,
+        )?;
+        Ok((public_input_path, non_determinism_path))
+    }
+
+    fn send_rewound(&self) {
+        let Some(ref action_tx) = self.action_tx else {
+            error!("action_tx must exist");
+            return;
+        };
+        let _ = action_tx.send(Action::Rewound(self.most_recent_ram_address));
+    }
+
+    /// The most recent cycle before the given one for which the predicate holds, if any. Leaves
+    /// Triton VM in some earlier state; use [`Self::travel_to_cycle`] afterwards.
+    fn latest_cycle_before(
+        &mut self,
+        cycle: u32,
+        mut predicate: impl FnMut(&mut Self) -> bool,
+    ) -> Option<u32> {
+        let mut segment_end = cycle;
+        for segment_start in self.history.checkpoint_cycles_before(cycle) {
+            let checkpoint = self.history.checkpoint_at_or_before(segment_start)?;
+            self.restore(checkpoint.clone());
+
+            let mut latest_match = None;
+            while self.vm_state.cycle_count < segment_end {
+                if predicate(self) {
+                    latest_match = Some(self.vm_state.cycle_count);
+                }
+                if self.execute_instruction().is_err() {
+                    break;
+                }
+            }
+            if latest_match.is_some() {
+                return latest_match;
+            }
+            segment_end = segment_start;
+        }
+        None
+    }
+
+    /// Travel back to the most recent cycle for which the predicate holds, or to the earliest
+    /// known cycle if there is no such cycle.
+    fn travel_back_until(&mut self, predicate: impl FnMut(&mut Self) -> bool) {
+        let current_cycle = self.vm_state.cycle_count;
+        if current_cycle <= self.history.earliest_cycle() {
+            self.warning = Some(anyhow!("already at the earliest known cycle"));
+            return;
+        }
+        let target_cycle = self
+            .latest_cycle_before(current_cycle, predicate)
+            .unwrap_or_else(|| self.history.earliest_cycle());
+        self.travel_to_cycle(target_cycle);
+    }
+
+    /// Handle [`Execute::ReverseContinue`].
+    fn reverse_continue(&mut self) {
+        self.travel_back_until(|state| state.at_breakpoint());
+        if self.at_breakpoint() {
+            self.record_breakpoint_hit();
+        }
+    }
+
+    /// Handle [`Execute::ReverseStep`].
+    fn reverse_step(&mut self, count: u32) {
+        let current_cycle = self.vm_state.cycle_count;
+        let earliest_cycle = self.history.earliest_cycle();
+        if current_cycle <= earliest_cycle {
+            self.warning = Some(anyhow!("already at the earliest known cycle"));
+            return;
+        }
+        let target_cycle = current_cycle.saturating_sub(count).max(earliest_cycle);
+        self.travel_to_cycle(target_cycle);
+    }
+
+    /// Handle [`Execute::ReverseNext`].
+    fn reverse_next(&mut self) {
+        let current_jump_stack_depth = self.vm_state.jump_stack.len();
+        self.travel_back_until(|state| state.vm_state.jump_stack.len() <= current_jump_stack_depth);
+    }
+
+    /// Handle [`Execute::ReverseFinish`].
+    fn reverse_finish(&mut self) {
+        let current_jump_stack_depth = self.vm_state.jump_stack.len();
+        if current_jump_stack_depth == 0 {
+            self.warning = Some(anyhow!("already in the outermost call frame"));
+            return;
+        }
+        self.travel_back_until(|state| state.vm_state.jump_stack.len() < current_jump_stack_depth);
+    }
+
+    /// Handle [`Execute::RunTo`].
+    fn run_to(&mut self, location: &Location) {
+        let address = match location.resolve(&self.vm_state.program) {
+            Ok(address) => address,
+            Err(report) => {
+                self.warning = Some(report);
+                return;
+            }
+        };
+        let current_jump_stack_depth = self.vm_state.jump_stack.len();
+        self.step();
+        while self.vm_is_running()
+            && self.vm_state.instruction_pointer != address
+            && self.vm_state.jump_stack.len() >= current_jump_stack_depth
+        {
+            if self.at_breakpoint() {
+                self.record_breakpoint_hit();
+                return;
+            }
+            self.step();
+        }
+    }
+
+    /// Handle [`Execute::GotoCycle`].
+    fn goto_cycle(&mut self, cycle: u32) {
+        self.travel_to_cycle(cycle);
+        let reached_cycle = self.vm_state.cycle_count;
+        if reached_cycle < cycle && self.vm_state.halting {
+            self.warning = Some(anyhow!("execution halts at cycle {reached_cycle}"));
+        }
+    }
+
+    /// Prove the run of the halted program on a background thread. The thread reports its
+    /// progress and its result as actions.
+    fn start_proving(&mut self) {
+        if !self.vm_state.halting {
+            self.warning = Some(anyhow!("only a halted program can be proven"));
+            return;
+        }
+        if let ProverStatus::Running { .. } = self.prover_status {
+            self.warning = Some(anyhow!("already proving"));
+            return;
+        }
+        if self.history.has_edits_after_start() {
+            // The prover re-executes from the initial state, which does not know about the edits.
+            let warning = "cannot prove a run whose public input was edited during execution; \
+                           restart with the edited input to prove it";
+            self.warning = Some(anyhow!(warning));
+            return;
+        }
+        let Some(action_tx) = self.action_tx.clone() else {
+            error!("action_tx must exist");
+            return;
+        };
+
+        let mut initial_state = self.history.initial_checkpoint().vm_state.clone();
+        initial_state.program = self.vm_state.program.clone();
+        let job = ProvingJob::next();
+        self.prover_status = ProverStatus::Running {
+            job,
+            phase: ProvingPhase::TracingExecution,
+            start: Instant::now(),
+        };
+        std::thread::spawn(move || {
+            let report = prover::prove(initial_state, |phase| {
+                let _ = action_tx.send(Action::ProvingProgress(job, phase));
+            });
+            let report = report.map_err(|err| err.to_string());
+            let _ = action_tx.send(Action::ProofGenerated(job, Box::new(report)));
+        });
+    }
+
+    fn record_proving_progress(&mut self, reporting_job: ProvingJob, new_phase: ProvingPhase) {
+        if let ProverStatus::Running {
+            job, ref mut phase, ..
+        } = self.prover_status
+            && job == reporting_job
+        {
+            *phase = new_phase;
+        }
+    }
+
+    /// Take note of the prover's result. Results of any job but the running one, like that of
+    /// a prover started before a reset or reload, are ignored.
+    fn record_proof(&mut self, reporting_job: ProvingJob, report: Result<ProofReport, String>) {
+        let ProverStatus::Running { job, .. } = self.prover_status else {
+            return;
+        };
+        if job != reporting_job {
+            return;
+        }
+        match report {
+            Ok(report) => {
+                self.info = Some(format!("{report}; save with “save-proof”"));
+                self.prover_status = ProverStatus::Done(Box::new(report));
+            }
+            Err(err) => {
+                self.warning = Some(anyhow!("proving failed: {err}"));
+                self.prover_status = ProverStatus::Idle;
+            }
+        }
+    }
+
+    fn save_proof(&mut self, directory: Option<&str>) {
+        let ProverStatus::Done(ref report) = self.prover_status else {
+            self.warning = Some(anyhow!("no proof to save; prove first"));
+            return;
+        };
+        let directory = directory.map_or_else(get_data_dir, PathBuf::from);
+        match report.save(&directory) {
+            Ok((proof_path, claim_path)) => {
+                let proof_path = proof_path.display();
+                let claim_path = claim_path.display();
+                self.info = Some(format!(
+                    "wrote proof to {proof_path}, claim to {claim_path}"
+                ));
+            }
+            Err(report) => self.warning = Some(report),
+        }
+    }
+
+    /// Change the pending public input. Execution continues differently from here on, which is
+    /// why all knowledge about later cycles is dropped. States before the edit keep the original
+    /// input, but execution reaching the edit's cycle again continues with the edited input.
+    fn edit_public_input(&mut self, edit: &InputEdit) {
+        let public_input = &mut self.vm_state.public_input;
+        match *edit {
+            InputEdit::Append(ref elements) => public_input.extend(elements),
+            InputEdit::Delete(index) if index < public_input.len() => {
+                public_input.remove(index);
+            }
+            InputEdit::Modify(index, element) if index < public_input.len() => {
+                public_input[index] = element;
+            }
+            InputEdit::Delete(index) | InputEdit::Modify(index, _) => {
+                let message = anyhow!("no pending public input element at index {index}");
+                self.warning = Some(message);
+                return;
+            }
+        }
+        self.forget_future();
+        self.history.record_edit(self.checkpoint());
+    }
+
+    /// Drop everything that depends on how execution continues from the current cycle.
+    fn forget_future(&mut self) {
+        let cycle = self.vm_state.cycle_count;
+        self.history.forget_after(cycle);
+        self.undo_stack.retain(|&undo_cycle| undo_cycle <= cycle);
+        self.redo_stack.clear();
+        self.furthest_cycle = cycle;
+        self.breakpoint_hit_cycles
+            .retain(|&hit_cycle| hit_cycle <= cycle);
+        self.anticipate_padded_height();
+    }
+
+    fn save_public_input(&mut self, path: &str) {
+        let public_input = self.vm_state.public_input.iter().join(" ");
+        if let Err(err) = fs::write(path, format!("{public_input}\n")) {
+            self.warning = Some(err.into());
+            return;
+        }
+        let num_elements = self.vm_state.public_input.len();
+        self.info = Some(format!(
+            "wrote {num_elements} pending public input element(s) to {path}"
+        ));
+    }
+
+    /// Re-execute until reaching the given position, for example after reloading the program.
+    /// Ignores breakpoints. Returns why execution stopped before reaching the position, if it
+    /// did.
+    pub fn replay_to(
+        &mut self,
+        position: &ExecutionPosition,
+        is_same_program: bool,
+    ) -> Option<String> {
+        let last_cycle = position.cycle.saturating_add(self.interrupt_cycle);
+        let reason_for_stopping = loop {
+            if position.is_reached_by(&self.vm_state, is_same_program) {
+                break None;
+            }
+            if self.vm_state.halting {
+                break Some("program halts".to_string());
+            }
+            if self.vm_state.cycle_count >= last_cycle {
+                break Some("position not reached before interrupt cycle".to_string());
+            }
+            if let Err(err) = self.execute_instruction() {
+                let reason = err.to_string();
+                self.error = Some(err);
+                break Some(reason);
+            }
+        };
+        self.send_rewound();
+        self.anticipate_padded_height();
+        reason_for_stopping
+    }
+
+    /// Determine the padded height after executing the next instruction. Allows warning about
+    /// the padded height growing to the next power of two.
+    fn anticipate_padded_height(&mut self) {
+        self.padded_height_after_next_instruction = self
+            .table_heights
+            .padded_height_after_next_instruction(&self.vm_state);
+    }
+
+    /// Write the execution profile to the given file. See [`Profile::export`] for the formats.
+    pub fn export_profile(&self, path: &str) -> Result<()> {
+        self.profile.export(&self.vm_state.program, Path::new(path))
+    }
+
+    /// Write the current VM state as JSON to the given file, or to a file in the data directory.
+    /// The file can be used as an `--initial-state`. If requested, the type hints are included
+    /// under an additional key. Returns the path of the written file.
+    pub fn dump_state(&self, path: Option<&str>, with_type_hints: bool) -> Result<PathBuf> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let data_dir = get_data_dir();
+                fs::create_dir_all(&data_dir)?;
+                let cycle = self.vm_state.cycle_count;
+                data_dir.join(format!("state_at_cycle_{cycle}.json"))
+            }
+        };
+
+        let mut state = serde_json::to_value(&self.vm_state)?;
+        if with_type_hints && let Some(state) = state.as_object_mut() {
+            let type_hints = serde_json::to_value(&self.type_hints)?;
+            state.insert(Self::TYPE_HINTS_KEY.to_string(), type_hints);
+        }
+        fs::write(&path, serde_json::to_string_pretty(&state)?)?;
+        Ok(path)
     }
 
     fn maybe_inform_about_interrupt(&mut self) {
@@ -236,15 +1130,16 @@ impl TritonVMState {
         }
     }
 
-    fn record_undo_information(&mut self) {
-        if self.vm_is_stopped() {
+    fn record_undo_information(&mut self, execute: &Execute) {
+        if self.vm_is_stopped() && !execute.affects_stopped_vm() {
             return;
         }
-        let undo_information = UndoInformation {
-            vm_state: self.vm_state.clone(),
-            type_hints: self.type_hints.clone(),
-        };
-        self.undo_stack.push(undo_information);
+        self.push_undo_information();
+    }
+
+    fn push_undo_information(&mut self) {
+        self.undo_stack.push(self.vm_state.cycle_count);
+        self.redo_stack.clear();
 
         let Some(ref action_tx) = self.action_tx else {
             error!("action_tx must exist");
@@ -253,15 +1148,56 @@ impl TritonVMState {
         let _ = action_tx.send(Action::RecordUndoInfo);
     }
 
+    /// Save the current state under the given name, replacing any checkpoint of the same name.
+    fn save_named_checkpoint(&mut self, name: &str) {
+        let named_checkpoint = NamedCheckpoint {
+            name: name.to_string(),
+            checkpoint: self.checkpoint(),
+        };
+        match self.named_checkpoints.iter_mut().find(|c| c.name == name) {
+            Some(existing) => *existing = named_checkpoint,
+            None => self.named_checkpoints.push(named_checkpoint),
+        }
+        let cycle = self.vm_state.cycle_count;
+        self.info = Some(format!("saved checkpoint “{name}” at cycle {cycle}"));
+    }
+
+    /// Jump to the named checkpoint. Can be undone.
+    fn restore_named_checkpoint(&mut self, name: &str) {
+        let Some(named_checkpoint) = self.named_checkpoints.iter().find(|c| c.name == name) else {
+            self.warning = Some(anyhow!("no checkpoint “{name}”"));
+            return;
+        };
+        let checkpoint = named_checkpoint.checkpoint.clone();
+        self.push_undo_information();
+        self.restore(checkpoint);
+        self.send_rewound();
+    }
+
+    fn delete_named_checkpoint(&mut self, name: &str) {
+        let num_checkpoints = self.named_checkpoints.len();
+        self.named_checkpoints.retain(|c| c.name != name);
+        if self.named_checkpoints.len() == num_checkpoints {
+            self.warning = Some(anyhow!("no checkpoint “{name}”"));
+        }
+    }
+
     fn program_undo(&mut self) {
-        let Some(undo_information) = self.undo_stack.pop() else {
+        let Some(cycle) = self.undo_stack.pop() else {
             self.warning = Some(anyhow!("no more undo information available"));
             return;
         };
-        self.warning = None;
-        self.error = None;
-        self.vm_state = undo_information.vm_state;
-        self.type_hints = undo_information.type_hints;
+        self.redo_stack.push(self.vm_state.cycle_count);
+        self.travel_to_cycle(cycle);
+    }
+
+    fn program_redo(&mut self) {
+        let Some(cycle) = self.redo_stack.pop() else {
+            self.warning = Some(anyhow!("nothing to redo"));
+            return;
+        };
+        self.undo_stack.push(self.vm_state.cycle_count);
+        self.travel_to_cycle(cycle);
     }
 }
 
@@ -273,8 +1209,71 @@ impl Component for TritonVMState {
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
-            Action::Execute(ref execute) => self.execute(execute),
-            Action::Undo => self.program_undo(),
+            Action::Execute(ref execute) => {
+                self.execute(execute);
+                self.anticipate_padded_height();
+            }
+            Action::Undo => {
+                self.program_undo();
+                self.anticipate_padded_height();
+            }
+            Action::Redo => {
+                self.program_redo();
+                self.anticipate_padded_height();
+            }
+            Action::ToggleBreakpointAt(address) => self.toggle_breakpoint(address),
+            Action::SetBreakpoint(ref location, ref condition) => {
+                self.set_breakpoint(location, condition.clone());
+            }
+            Action::DeleteBreakpoint(ref location) => self.delete_breakpoint(location),
+            Action::SetWatchpoint(watchpoint) => self.set_watchpoint(watchpoint),
+            Action::DeleteWatchpoints(addresses) => self.delete_watchpoints(addresses),
+            Action::SetCatchpoint(ref catchpoint) => self.set_catchpoint(catchpoint.clone()),
+            Action::DeleteCatchpoint(ref catchpoint) => self.delete_catchpoint(catchpoint),
+            Action::ExportProfile(ref path) => {
+                if let Err(report) = self.export_profile(path) {
+                    self.warning = Some(report);
+                }
+            }
+            Action::EditPublicInput(ref edit) => self.edit_public_input(edit),
+            Action::SavePublicInput(ref path) => self.save_public_input(path),
+            Action::EnterInput(ref elements) => {
+                self.enter_input(elements);
+                self.anticipate_padded_height();
+            }
+            Action::ExportInput(ref directory) => {
+                let directory = directory
+                    .as_deref()
+                    .map_or_else(get_data_dir, PathBuf::from);
+                match self.export_input(&directory) {
+                    Ok((public_input_path, non_determinism_path)) => {
+                        let public_input_path = public_input_path.display();
+                        let non_determinism_path = non_determinism_path.display();
+                        self.info = Some(format!(
+                            "wrote public input to {public_input_path}, \
+                            non-determinism to {non_determinism_path}"
+                        ));
+                    }
+                    Err(report) => self.warning = Some(report),
+                }
+            }
+            Action::Prove => self.start_proving(),
+            Action::ProvingProgress(job, phase) => self.record_proving_progress(job, phase),
+            Action::ProofGenerated(job, report) => self.record_proof(job, *report),
+            Action::SaveProof(ref directory) => self.save_proof(directory.as_deref()),
+            Action::SaveCheckpoint(ref name) => self.save_named_checkpoint(name),
+            Action::RestoreCheckpoint(ref name) => {
+                self.restore_named_checkpoint(name);
+                self.anticipate_padded_height();
+            }
+            Action::DeleteCheckpoint(ref name) => self.delete_named_checkpoint(name),
+            Action::DumpState(ref path, with_type_hints) => {
+                match self.dump_state(path.as_deref(), with_type_hints) {
+                    Ok(path) => self.info = Some(format!("wrote state to {}", path.display())),
+                    Err(report) => self.warning = Some(report),
+                }
+            }
+            Action::Error(ref message) => self.warning = Some(anyhow!("{message}")),
             _ => (),
         }
         Ok(None)
@@ -284,6 +1283,7 @@ impl Component for TritonVMState {
 #[cfg(test)]
 mod tests {
     use assert2::assert;
+    use assert2::let_assert;
     use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
@@ -291,9 +1291,20 @@ mod tests {
 
     use crate::args_tests::args_for_test_program_with_initial_state;
     use crate::args_tests::args_for_test_program_with_test_input;
+    use crate::watchpoint::WatchMode;
 
     use super::*;
 
+    fn state_with_program(program: Program) -> TritonVMState {
+        state_with_program_and_input(program, PublicInput::default())
+    }
+
+    fn state_with_program_and_input(program: Program, public_input: PublicInput) -> TritonVMState {
+        let vm_state = VMState::new(program, public_input, NonDeterminism::default());
+        let type_hints = ShadowMemory::new_for_default_initial_state();
+        TritonVMState::from_vm_state(vm_state, Some(type_hints), &TuiArgs::default())
+    }
+
     #[proptest]
     fn presumed_top_of_stack_is_actually_top_of_stack(
         #[strategy(vec(arb(), NUM_OP_STACK_REGISTERS..100))] stack: Vec<BFieldElement>,
@@ -328,10 +1339,635 @@ mod tests {
         println!("{serialized}");
     }
 
+    #[test]
+    fn editing_public_input_forgets_the_future() {
+        let program = triton_program!(read_io 1 pop 1 push 0 pop 1 halt);
+        let mut state = state_with_program_and_input(program, bfe_vec![1, 2].into());
+        state.execute(&Execute::Step(4));
+        state.execute(&Execute::GotoCycle(1));
+        assert!(4 == state.furthest_cycle);
+
+        state.edit_public_input(&InputEdit::Append(bfe_vec![3]));
+        assert!(1 == state.furthest_cycle);
+        assert!(bfe_vec![2, 3] == Vec::from(state.vm_state.public_input.clone()));
+        assert!(state.history.checkpoint_at_or_before(4).unwrap().cycle() <= 1);
+
+        state.edit_public_input(&InputEdit::Delete(5));
+        assert!(state.warning.is_some());
+    }
+
+    #[test]
+    fn edited_public_input_survives_travelling_back_and_forth() {
+        let program = triton_program!(read_io 1 pop 1 read_io 1 pop 1 halt);
+        let mut state = state_with_program_and_input(program, bfe_vec![1, 2].into());
+        for _ in 0..3 {
+            state.execute(&Execute::Step(1));
+        }
+        state.execute(&Execute::GotoCycle(2));
+
+        state.edit_public_input(&InputEdit::Modify(0, bfe!(9)));
+        assert!(state.undo_stack.iter().all(|&cycle| cycle <= 2));
+        state.execute(&Execute::Step(1));
+        assert!(Some(&bfe!(9)) == state.vm_state.op_stack.stack.last());
+        state.execute(&Execute::ReverseStep(1));
+        assert!(bfe_vec![9] == Vec::from(state.vm_state.public_input.clone()));
+
+        state.execute(&Execute::GotoCycle(0));
+        assert!(bfe_vec![1, 2] == Vec::from(state.vm_state.public_input.clone()));
+        state.execute(&Execute::Step(2));
+        assert!(bfe_vec![9] == Vec::from(state.vm_state.public_input.clone()));
+    }
+
+    #[test]
+    fn missing_input_is_asked_for_and_entered_input_is_kept() {
+        let program = triton_program!(read_io 2 divine 1 add add write_io 1 halt);
+        let mut state = state_with_program_and_input(program, bfe_vec![1].into());
+        state.interactive_input = true;
+
+        state.execute(&Execute::Continue(1));
+        let_assert!(Some(input_request) = state.input_request);
+        assert!(input_request.is_for_public_input());
+        assert!(1 == input_request.num_missing);
+        assert!(0 == state.vm_state.cycle_count);
+
+        state.enter_input(&bfe_array![2]);
+        let_assert!(Some(input_request) = state.input_request);
+        assert!(!input_request.is_for_public_input());
+        state.enter_input(&bfe_array![3]);
+        assert!(state.vm_state.halting);
+        assert!(bfe_vec![6] == state.vm_state.public_output);
+
+        state.execute(&Execute::GotoCycle(0));
+        state.execute(&Execute::Continue(1));
+        assert!(state.vm_state.halting);
+        assert!(None == state.input_request);
+
+        let directory = std::env::temp_dir().join("triton_tui_exported_input");
+        let_assert!(Ok((public_input_path, non_determinism_path)) = state.export_input(&directory));
+        assert!("1 2\n" == fs::read_to_string(public_input_path).unwrap());
+        let non_determinism = fs::read_to_string(non_determinism_path).unwrap();
+        let non_determinism = serde_json::from_str::<NonDeterminism>(&non_determinism).unwrap();
+        assert!(bfe_vec![3] == non_determinism.individual_tokens);
+    }
+
+    #[test]
+    fn stepping_interrupted_by_input_request_takes_the_requested_number_of_steps() {
+        let program = triton_program!(push 1 push 2 read_io 1 pop 3 push 4 push 5 push 6 halt);
+        let mut state = state_with_program(program);
+        state.interactive_input = true;
+
+        state.execute(&Execute::Step(4));
+        assert!(2 == state.vm_state.cycle_count);
+        assert!(state.input_request.is_some());
+        state.enter_input(&bfe_array![3]);
+        assert!(4 == state.vm_state.cycle_count);
+        assert!(None == state.input_request);
+    }
+
+    #[test]
+    fn next_interrupted_by_input_request_steps_over_the_call() {
+        let program = triton_program!(call foo push 1 halt foo: read_io 1 pop 1 return);
+        let mut state = state_with_program(program);
+        state.interactive_input = true;
+
+        state.execute(&Execute::Next(1));
+        assert!(1 == state.vm_state.jump_stack.len());
+        assert!(state.input_request.is_some());
+        state.enter_input(&bfe_array![3]);
+        assert!(state.vm_state.jump_stack.is_empty());
+        assert!(4 == state.vm_state.cycle_count);
+    }
+
+    #[test]
+    fn missing_input_is_an_error_unless_asked_for() {
+        let program = triton_program!(read_io 1 halt);
+        let mut state = state_with_program(program);
+        state.execute(&Execute::Step(1));
+        assert!(None == state.input_request);
+        assert!(state.error.is_some());
+    }
+
+    #[test]
+    fn continue_stops_at_runtime_breakpoint() {
+        let program = triton_program!(push 1 push 2 push 3 pop 3 halt);
+        let mut state = state_with_program(program);
+
+        state.toggle_breakpoint(6);
+        state.continue_execution();
+        assert!(6 == state.vm_state.instruction_pointer);
+
+        state.toggle_breakpoint(6);
+        state.continue_execution();
+        assert!(state.vm_state.halting);
+    }
+
+    #[test]
+    fn continue_stops_at_conditional_breakpoint_only_if_condition_holds() {
+        let program =
+            triton_program!(push 3 call loop halt loop: push -1 add dup 0 skiz recurse return);
+        let mut state = state_with_program(program);
+
+        let condition = Condition::parse("st0 == 1").unwrap();
+        let location = Location::Label("loop".to_string());
+        state.set_breakpoint(&location, Some(condition));
+        state.continue_execution();
+        assert!(5 == state.vm_state.instruction_pointer);
+        assert!(Some(&bfe!(1)) == state.vm_state.op_stack.stack.last());
+
+        state.delete_breakpoint(&location);
+        state.continue_execution();
+        assert!(state.vm_state.halting);
+    }
+
+    #[test]
+    fn unevaluable_breakpoint_condition_stops_execution_with_warning() {
+        let program = triton_program!(push 1 push 2 pop 2 halt);
+        let mut state = state_with_program(program);
+
+        let condition = Condition::parse("no_such_variable == 0").unwrap();
+        state.set_breakpoint(&Location::Address(2), Some(condition));
+        state.continue_execution();
+        assert!(2 == state.vm_state.instruction_pointer);
+        assert!(state.warning.is_some());
+    }
+
+    #[test]
+    fn continue_stops_right_after_write_to_watched_address() {
+        let program = triton_program!(
+            push 7 push 42 write_mem 1 pop 1
+            push 0 push 8 push 41 write_mem 2 pop 1
+            halt
+        );
+        let mut state = state_with_program(program);
+        state.set_watchpoint(Watchpoint {
+            addresses: AddressRange::parse("42").unwrap(),
+            mode: WatchMode::Write,
+        });
+
+        state.execute(&Execute::Continue(1));
+        assert!(6 == state.vm_state.instruction_pointer);
+        let_assert!([hit] = &state.watchpoint_hits[..]);
+        assert!(bfe!(42) == hit.access.address);
+        assert!(bfe!(0) == hit.old_value);
+        assert!(bfe!(7) == hit.new_value);
+
+        state.execute(&Execute::Continue(1));
+        assert!(16 == state.vm_state.instruction_pointer);
+        let_assert!([hit] = &state.watchpoint_hits[..]);
+        assert!(bfe!(7) == hit.old_value);
+        assert!(bfe!(0) == hit.new_value);
+
+        state.execute(&Execute::Continue(1));
+        assert!(state.vm_state.halting);
+        assert!(state.watchpoint_hits.is_empty());
+    }
+
+    #[test]
+    fn read_watchpoint_ignores_writes() {
+        let program = triton_program!(
+            push 7 push 42 write_mem 1
+            read_mem 1 pop 2
+            halt
+        );
+        let mut state = state_with_program(program);
+        state.set_watchpoint(Watchpoint {
+            addresses: AddressRange::parse("40..50").unwrap(),
+            mode: WatchMode::Read,
+        });
+
+        state.execute(&Execute::Continue(1));
+        assert!(8 == state.vm_state.instruction_pointer);
+        let_assert!([hit] = &state.watchpoint_hits[..]);
+        assert!(!hit.access.is_write);
+        assert!(bfe!(43) == hit.access.address);
+    }
+
+    #[test]
+    fn continue_stops_at_catchpoints() {
+        let program = triton_program!(
+            push 1 call foo write_io 1
+            push 0 push 0 push 0 push 0 push 0 hash halt
+            foo: return
+        );
+        let mut state = state_with_program(program);
+        state.set_catchpoint(Catchpoint::parse("call foo").unwrap());
+        state.set_catchpoint(Catchpoint::parse("io").unwrap());
+        state.set_catchpoint(Catchpoint::parse("hash").unwrap());
+
+        state.execute(&Execute::Continue(1));
+        assert!(Some(Catchpoint::parse("call foo").unwrap()) == state.catchpoint_hit);
+        assert!(18 == state.vm_state.instruction_pointer);
+
+        state.execute(&Execute::Continue(1));
+        assert!(Some(Catchpoint::Io) == state.catchpoint_hit);
+        assert!(4 == state.vm_state.instruction_pointer);
+
+        state.execute(&Execute::Continue(1));
+        assert!(Some(Catchpoint::parse("hash").unwrap()) == state.catchpoint_hit);
+        assert!(16 == state.vm_state.instruction_pointer);
+
+        state.execute(&Execute::Continue(1));
+        assert!(state.vm_state.halting);
+        assert!(None == state.catchpoint_hit);
+    }
+
+    fn count_down_program(start: u32) -> Program {
+        triton_program!(
+            push {start}
+            hint counter = stack[0]
+            call count_down
+            halt
+            count_down:
+                push -1 add
+                dup 0 push 0 push 1 write_mem 1 pop 1
+                dup 0 skiz recurse
+                return
+        )
+    }
+
+    #[test]
+    fn travelling_back_restores_exact_earlier_state() {
+        let mut state = state_with_program(count_down_program(700));
+        let earlier_cycle = History::INITIAL_CHECKPOINT_INTERVAL + 17;
+        while state.vm_state.cycle_count < earlier_cycle {
+            state.step();
+        }
+        let earlier_state = state.checkpoint();
+
+        state.execute(&Execute::Continue(1));
+        assert!(state.vm_state.halting);
+        let final_cycle = state.vm_state.cycle_count;
+
+        state.execute(&Execute::ReverseStep(1));
+        assert!(final_cycle - 1 == state.vm_state.cycle_count);
+        assert!(!state.vm_state.halting);
+
+        state.travel_to_cycle(earlier_cycle);
+        assert!(earlier_state.vm_state == state.checkpoint().vm_state);
+        assert!(earlier_state.type_hints == state.type_hints);
+        assert!(earlier_state.most_recent_ram_address == state.most_recent_ram_address);
+    }
+
+    #[test]
+    fn reverse_continue_stops_at_breakpoints_in_reverse() {
+        let mut state = state_with_program(count_down_program(3));
+        let_assert!(
+            Ok(address) =
+                Location::Label("count_down".to_string()).resolve(&state.vm_state.program)
+        );
+        state.execute(&Execute::Continue(1));
+        assert!(state.vm_state.halting);
+
+        state.toggle_breakpoint(address);
+        let counter = |state: &TritonVMState| state.vm_state.op_stack.stack.last().unwrap().value();
+        for expected_counter in [1, 2, 3] {
+            state.execute(&Execute::ReverseContinue(1));
+            assert!(address == state.vm_state.instruction_pointer);
+            assert!(expected_counter == counter(&state));
+        }
+
+        state.execute(&Execute::ReverseContinue(1));
+        assert!(0 == state.vm_state.cycle_count);
+        state.execute(&Execute::ReverseContinue(1));
+        assert!(state.warning.is_some());
+    }
+
+    #[test]
+    fn reverse_next_steps_over_calls_and_reverse_finish_leaves_them() {
+        let program = triton_program!(push 1 call foo push 2 halt foo: push 3 pop 1 return);
+        let mut state = state_with_program(program);
+        while state.vm_state.instruction_pointer != 4 {
+            state.step();
+        }
+        state.execute(&Execute::ReverseNext(1));
+        assert!(2 == state.vm_state.instruction_pointer);
+        state.execute(&Execute::ReverseNext(1));
+        assert!(0 == state.vm_state.instruction_pointer);
+
+        while state.vm_state.instruction_pointer != 9 {
+            state.step();
+        }
+        state.execute(&Execute::ReverseFinish(1));
+        assert!(2 == state.vm_state.instruction_pointer);
+        assert!(state.vm_state.jump_stack.is_empty());
+
+        state.execute(&Execute::ReverseFinish(1));
+        assert!(state.warning.is_some());
+    }
+
+    #[test]
+    fn undo_reverts_reverse_execution() {
+        let mut state = state_with_program(count_down_program(3));
+        state.execute(&Execute::Continue(1));
+        let final_state = state.vm_state.clone();
+
+        state.execute(&Execute::ReverseStep(1));
+        state.execute(&Execute::ReverseStep(1));
+        state.program_undo();
+        state.program_undo();
+        assert!(final_state == state.vm_state);
+    }
+
+    #[test]
+    fn redo_reverts_undo_until_execution_diverges() {
+        let mut state = state_with_program(count_down_program(3));
+        state.execute(&Execute::Step(1));
+        state.execute(&Execute::Step(4));
+        let later_state = state.checkpoint();
+
+        state.program_undo();
+        state.program_undo();
+        assert!(0 == state.vm_state.cycle_count);
+        state.program_redo();
+        assert!(1 == state.vm_state.cycle_count);
+        state.program_redo();
+        assert!(later_state.vm_state == state.checkpoint().vm_state);
+        assert!(later_state.type_hints == state.type_hints);
+
+        state.program_redo();
+        assert!(state.warning.is_some());
+
+        state.program_undo();
+        state.execute(&Execute::Step(1));
+        assert!(state.redo_stack.is_empty());
+        state.program_redo();
+        assert!(2 == state.vm_state.cycle_count);
+        assert!(state.warning.is_some());
+    }
+
+    #[test]
+    fn undo_restores_exact_state_even_if_history_exceeds_memory_limit() {
+        let mut state = state_with_program(count_down_program(700));
+        state.history = History::new(state.checkpoint(), 0);
+        for _ in 0..20 {
+            state.execute(&Execute::Step(1));
+        }
+        let earlier_state = state.checkpoint();
+
+        state.execute(&Execute::Continue(1));
+        assert!(state.vm_state.halting);
+        assert!(1 == state.history.num_checkpoints());
+
+        state.program_undo();
+        assert!(earlier_state.vm_state == state.checkpoint().vm_state);
+        assert!(earlier_state.type_hints == state.type_hints);
+        assert!(earlier_state.most_recent_ram_address == state.most_recent_ram_address);
+    }
+
+    #[test]
+    fn repeated_execution_stops_early_if_vm_stops() {
+        let program =
+            triton_program!(push 3 call loop halt loop: push -1 add dup 0 skiz recurse return);
+        let mut state = state_with_program(program);
+        state.execute(&Execute::Step(4));
+        assert!(4 == state.vm_state.cycle_count);
+
+        state.set_breakpoint(&Location::Label("loop".to_string()), None);
+        state.execute(&Execute::Continue(2));
+        assert!(12 == state.vm_state.cycle_count);
+
+        state.execute(&Execute::ReverseStep(5));
+        assert!(7 == state.vm_state.cycle_count);
+
+        state.execute(&Execute::Next(1_000));
+        assert!(state.vm_state.halting);
+        state.execute(&Execute::ReverseStep(1_000));
+        assert!(0 == state.vm_state.cycle_count);
+    }
+
+    #[test]
+    fn run_to_stops_at_location_or_earlier_breakpoint() {
+        let program = triton_program!(push 1 call foo push 2 pop 3 halt foo: push 3 return);
+        let mut state = state_with_program(program);
+        let foo = Location::Label("foo".to_string());
+
+        state.execute(&Execute::RunTo(Location::Address(4)));
+        assert!(4 == state.vm_state.instruction_pointer);
+        assert!(state.vm_state.jump_stack.is_empty());
+
+        state.program_undo();
+        state.set_breakpoint(&foo, None);
+        state.execute(&Execute::RunTo(Location::Address(6)));
+        assert!(9 == state.vm_state.instruction_pointer);
+
+        state.execute(&Execute::RunTo(Location::Address(6)));
+        assert!(4 == state.vm_state.instruction_pointer);
+        state.execute(&Execute::RunTo(Location::Address(6)));
+        assert!(6 == state.vm_state.instruction_pointer);
+
+        state.execute(&Execute::RunTo(foo));
+        assert!(state.vm_state.halting);
+    }
+
+    #[test]
+    fn run_to_stops_once_the_current_call_returns() {
+        let program = triton_program!(call foo push 2 pop 1 halt foo: push 3 pop 1 return);
+        let mut state = state_with_program(program);
+        state.execute(&Execute::Step(1));
+        assert!(1 == state.vm_state.jump_stack.len());
+
+        state.execute(&Execute::RunTo(Location::Address(6)));
+        assert!(state.vm_state.jump_stack.is_empty());
+        assert!(2 == state.vm_state.instruction_pointer);
+    }
+
+    #[test]
+    fn goto_cycle_into_infinite_loop_is_interrupted() {
+        let program = triton_program!(call forever halt forever: recurse);
+        let mut state = state_with_program(program);
+        state.interrupt_cycle = 100;
+        state.execute(&Execute::GotoCycle(20_000));
+        assert!(100 == state.vm_state.cycle_count);
+        let_assert!(Some(warning) = &state.warning);
+        assert!(warning.to_string().contains("interrupted"));
+
+        state.execute(&Execute::GotoCycle(50));
+        state.execute(&Execute::GotoCycle(100));
+        assert!(100 == state.vm_state.cycle_count);
+        assert!(state.warning.is_none());
+    }
+
+    #[test]
+    fn goto_cycle_travels_forwards_and_backwards() {
+        let target_cycle = History::INITIAL_CHECKPOINT_INTERVAL + 3;
+        let mut reference = state_with_program(count_down_program(700));
+        for _ in 0..target_cycle {
+            reference.step();
+        }
+        let target_state = reference.checkpoint();
+
+        let mut state = state_with_program(count_down_program(700));
+        state.execute(&Execute::GotoCycle(target_cycle));
+        assert!(target_state.vm_state == state.checkpoint().vm_state);
+
+        state.execute(&Execute::Continue(1));
+        assert!(state.vm_state.halting);
+        let final_cycle = state.vm_state.cycle_count;
+        assert!(final_cycle == state.furthest_cycle);
+
+        state.execute(&Execute::GotoCycle(target_cycle));
+        assert!(target_state.vm_state == state.checkpoint().vm_state);
+        assert!(target_state.type_hints == state.type_hints);
+
+        state.execute(&Execute::GotoCycle(final_cycle + 10));
+        assert!(final_cycle == state.vm_state.cycle_count);
+        assert!(state.warning.is_some());
+
+        state.program_undo();
+        state.program_undo();
+        assert!(final_cycle == state.vm_state.cycle_count);
+    }
+
+    #[test]
+    fn cycles_of_breakpoint_hits_are_recorded() {
+        let program =
+            triton_program!(push 3 call loop halt loop: push -1 add dup 0 skiz recurse return);
+        let mut state = state_with_program(program);
+        state.set_breakpoint(&Location::Label("loop".to_string()), None);
+        while !state.vm_state.halting {
+            state.execute(&Execute::Continue(1));
+        }
+        let hit_cycles = state.breakpoint_hit_cycles.iter().copied().collect_vec();
+        assert!(vec![2, 7, 12] == hit_cycles);
+    }
+
     #[test]
     fn starting_tui_with_initial_state_makes_type_hint_stack_have_correct_length() {
         let args = args_for_test_program_with_initial_state();
         let state = TritonVMState::new(&args).unwrap();
         assert!(state.vm_state.op_stack.len() == state.type_hints.stack.len());
     }
+
+    #[test]
+    fn dumped_type_hints_must_match_op_stack() {
+        let mut state = state_with_program(triton_program!(push 1 halt));
+        let path = std::env::temp_dir().join("triton_tui_mismatched_type_hints.json");
+        let path = path.display().to_string();
+        state.type_hints.stack.push(None);
+        let_assert!(Ok(_) = state.dump_state(Some(&path), true));
+
+        let args = TuiArgs {
+            input_args: None,
+            initial_state: Some(path.clone()),
+            ..TuiArgs::default()
+        };
+        let_assert!(Err(_) = TritonVMState::new(&args));
+
+        let_assert!(Ok(_) = state.dump_state(Some(&path), false));
+        let_assert!(Ok(loaded) = TritonVMState::new(&args));
+        assert!(state.vm_state == loaded.vm_state);
+    }
+
+    #[test]
+    fn replaying_to_position_stops_where_reloaded_program_diverges() {
+        let position = ExecutionPosition {
+            cycle: 4,
+            function: None,
+        };
+        let mut state = state_with_program(triton_program!(push 1 push 2 push 3 pop 3 halt));
+        assert!(None == state.replay_to(&position, true));
+        assert!(4 == state.vm_state.cycle_count);
+
+        let mut state = state_with_program(triton_program!(push 1 pop 2 halt));
+        let_assert!(Some(_) = state.replay_to(&position, false));
+        assert!(1 == state.vm_state.cycle_count);
+        assert!(state.error.is_some());
+
+        let mut state = state_with_program(triton_program!(halt));
+        let_assert!(Some(reason) = state.replay_to(&position, false));
+        assert!("program halts" == reason);
+    }
+
+    #[test]
+    fn results_of_stale_prover_jobs_are_ignored() {
+        let mut state = state_with_program(triton_program!(halt));
+        let job = ProvingJob::next();
+        state.prover_status = ProverStatus::Running {
+            job,
+            phase: ProvingPhase::Proving,
+            start: Instant::now(),
+        };
+
+        let stale_job = ProvingJob::next();
+        state.record_proving_progress(stale_job, ProvingPhase::Verifying);
+        state.record_proof(stale_job, Err("stale".to_string()));
+        let_assert!(ProverStatus::Running { phase, .. } = &state.prover_status);
+        assert!(ProvingPhase::Proving == *phase);
+
+        state.record_proof(job, Err("current".to_string()));
+        assert!(ProverStatus::Idle == state.prover_status);
+        assert!(state.warning.is_some());
+    }
+
+    #[test]
+    fn breakpoints_follow_their_label_into_a_changed_program() {
+        let mut previous = state_with_program(triton_program!(
+            push 1 call foo call bar halt
+            foo: push 2 pop 1 return
+            bar: return
+        ));
+        for label in ["foo", "bar"] {
+            let location = Location::Label(label.to_string());
+            previous.set_breakpoint(&location, None);
+        }
+        previous.set_breakpoint(&Location::Address(9), None);
+        for catchpoint in ["call bar", "hash"] {
+            previous.set_catchpoint(Catchpoint::parse(catchpoint).unwrap());
+        }
+        assert!(vec![7, 9, 12] == previous.breakpoints.keys().copied().collect_vec());
+
+        let mut state = state_with_program(triton_program!(
+            call foo halt
+            foo: push 2 pop 1 return
+        ));
+        let dropped = state.adopt_breakpoints_of(&previous);
+        let expected = [
+            "breakpoint at 9",
+            "breakpoint at bar",
+            "catchpoint “call bar”",
+        ];
+        assert!(expected.to_vec() == dropped);
+        assert!(vec![3] == state.breakpoints.keys().copied().collect_vec());
+        assert!(vec![Catchpoint::parse("hash").unwrap()] == state.catchpoints);
+        assert!(state.warning.is_none());
+    }
+
+    #[test]
+    fn run_with_edited_public_input_is_not_proven() {
+        let program = triton_program!(read_io 1 pop 1 read_io 1 pop 1 halt);
+        let mut state = state_with_program_and_input(program, bfe_vec![1, 2].into());
+        let (action_tx, _action_rx) = tokio::sync::mpsc::unbounded_channel();
+        state.register_action_handler(action_tx).unwrap();
+        state.execute(&Execute::Step(2));
+        state.edit_public_input(&InputEdit::Modify(0, bfe!(3)));
+        state.execute(&Execute::Continue(1));
+        assert!(state.vm_state.halting);
+
+        state.update(Action::Prove).unwrap();
+        assert!(ProverStatus::Idle == state.prover_status);
+        let_assert!(Some(warning) = &state.warning);
+        assert!(warning.to_string().contains("edited"));
+    }
+
+    #[test]
+    fn halted_program_can_be_proven_in_the_background() {
+        let mut state = state_with_program(triton_program!(push 1 pop 1 halt));
+        let (action_tx, mut action_rx) = tokio::sync::mpsc::unbounded_channel();
+        state.register_action_handler(action_tx).unwrap();
+        state.update(Action::Prove).unwrap();
+        assert!(state.warning.is_some());
+
+        state.update(Action::Execute(Execute::Continue(1))).unwrap();
+        state.update(Action::Prove).unwrap();
+        assert!(let ProverStatus::Running { .. } = state.prover_status);
+        while let Some(action) = action_rx.blocking_recv() {
+            let is_done = matches!(action, Action::ProofGenerated(..));
+            state.update(action).unwrap();
+            if is_done {
+                break;
+            }
+        }
+        let_assert!(ProverStatus::Done(report) = &state.prover_status);
+        assert!(report.is_valid);
+    }
 }
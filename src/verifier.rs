@@ -0,0 +1,121 @@
+//! Checking an existing proof against its claim, for example one written by `save-proof`.
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::anyhow;
+use fs_err as fs;
+use triton_vm::prelude::*;
+
+/// The outcome of verifying a proof, together with everything worth knowing about the claim.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Verification {
+    pub proof_path: String,
+    pub claim_path: String,
+    pub claim: Claim,
+
+    /// The size of the proof in bytes.
+    pub proof_size: usize,
+
+    /// The padded height the proof claims, or `None` if the proof is too malformed to tell.
+    pub padded_height: Option<usize>,
+
+    /// Why verification failed, or `None` if the proof is valid.
+    pub failure: Option<String>,
+
+    /// Whether the digest of the given program matches the claim, or `None` if no program was
+    /// given.
+    pub program_digest_matches: Option<bool>,
+}
+
+impl Verification {
+    /// Load proof and claim from the given JSON files and verify them. If a program is given,
+    /// also check that the claim is about it.
+    pub fn load(proof_path: &str, claim_path: &str, program: Option<&Program>) -> Result<Self> {
+        let proof = fs::read_to_string(proof_path)?;
+        let proof = serde_json::from_str::<Proof>(&proof)
+            .map_err(|err| anyhow!("proof “{proof_path}”: {err}"))?;
+        let claim = fs::read_to_string(claim_path)?;
+        let claim = serde_json::from_str::<Claim>(&claim)
+            .map_err(|err| anyhow!("claim “{claim_path}”: {err}"))?;
+
+        let failure = Stark::default()
+            .verify(&claim, &proof)
+            .err()
+            .map(|err| err.to_string());
+        let program_digest_matches = program.map(|p| p.hash() == claim.program_digest);
+        Ok(Self {
+            proof_path: proof_path.to_string(),
+            claim_path: claim_path.to_string(),
+            proof_size: proof.0.len() * size_of::<BFieldElement>(),
+            padded_height: proof.padded_height().ok(),
+            claim,
+            failure,
+            program_digest_matches,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use assert2::assert;
+    use assert2::let_assert;
+
+    use crate::prover;
+
+    use super::*;
+
+    fn saved_proof(directory: &str, program: Program) -> (String, String) {
+        let vm_state = VMState::new(program, bfe_vec![7].into(), [].into());
+        let report = prover::prove(vm_state, |_| ()).unwrap();
+        let directory = temp_dir().join(directory);
+        let (proof_path, claim_path) = report.save(&directory).unwrap();
+        (
+            proof_path.display().to_string(),
+            claim_path.display().to_string(),
+        )
+    }
+
+    #[test]
+    fn saved_proof_is_valid_and_about_the_proven_program() {
+        let program = triton_program!(read_io 1 write_io 1 halt);
+        let (proof_path, claim_path) = saved_proof("triton_tui_verify_valid", program.clone());
+        let_assert!(Ok(verification) = Verification::load(&proof_path, &claim_path, None));
+        assert!(None == verification.failure);
+        assert!(None == verification.program_digest_matches);
+        assert!(bfe_vec![7] == verification.claim.output);
+        assert!(verification.padded_height.is_some());
+
+        let_assert!(
+            Ok(verification) = Verification::load(&proof_path, &claim_path, Some(&program))
+        );
+        assert!(Some(true) == verification.program_digest_matches);
+
+        let other_program = triton_program!(halt);
+        let other_program = Some(&other_program);
+        let_assert!(Ok(verification) = Verification::load(&proof_path, &claim_path, other_program));
+        assert!(Some(false) == verification.program_digest_matches);
+    }
+
+    #[test]
+    fn proof_does_not_verify_against_altered_claim() {
+        let program = triton_program!(read_io 1 write_io 1 halt);
+        let (proof_path, claim_path) = saved_proof("triton_tui_verify_altered", program);
+        let claim = fs::read_to_string(&claim_path).unwrap();
+        let mut claim = serde_json::from_str::<Claim>(&claim).unwrap();
+        claim.output = bfe_vec![8];
+        fs::write(&claim_path, serde_json::to_string(&claim).unwrap()).unwrap();
+
+        let_assert!(Ok(verification) = Verification::load(&proof_path, &claim_path, None));
+        assert!(verification.failure.is_some());
+    }
+
+    #[test]
+    fn malformed_proof_is_rejected() {
+        let path = temp_dir().join("triton_tui_malformed_claim.json");
+        fs::write(&path, "{\"program_digest\": 3}").unwrap();
+        let path = path.display().to_string();
+        let_assert!(Err(err) = Verification::load(&path, &path, None));
+        assert!(err.to_string().contains("proof"));
+    }
+}
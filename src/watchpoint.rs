@@ -0,0 +1,169 @@
+use std::fmt;
+use std::fmt::Display;
+
+use color_eyre::eyre::Result;
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
+use serde::Serialize;
+use triton_vm::prelude::BFieldElement;
+
+use crate::action::RamAccess;
+
+/// Stops execution right after an instruction accessed any of the watched RAM addresses.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct Watchpoint {
+    pub addresses: AddressRange,
+    pub mode: WatchMode,
+}
+
+/// The kind of RAM access a [`Watchpoint`] reacts to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub(crate) enum WatchMode {
+    Read,
+    Write,
+
+    /// Both reads and writes.
+    Access,
+}
+
+/// A contiguous, non-empty range of RAM addresses. Both ends are inclusive.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct AddressRange {
+    start: u64,
+    end: u64,
+}
+
+/// A RAM access that triggered a [`Watchpoint`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct WatchpointHit {
+    pub access: RamAccess,
+    pub old_value: BFieldElement,
+    pub new_value: BFieldElement,
+}
+
+impl Watchpoint {
+    pub fn is_triggered_by(&self, access: RamAccess) -> bool {
+        let mode_matches = match self.mode {
+            WatchMode::Read => !access.is_write,
+            WatchMode::Write => access.is_write,
+            WatchMode::Access => true,
+        };
+        mode_matches && self.addresses.contains(access.address)
+    }
+}
+
+impl AddressRange {
+    /// Parse a single address like `42`, or a range like `40..50` or `40..=49`.
+    pub fn parse(range: &str) -> Result<Self> {
+        let parse_address = |address: &str| {
+            address
+                .trim()
+                .parse::<BFieldElement>()
+                .map(|address| address.value())
+                .map_err(|err| anyhow!("invalid address “{address}”: {err}"))
+        };
+
+        let Some((start, end)) = range.split_once("..") else {
+            let address = parse_address(range)?;
+            return Ok(Self {
+                start: address,
+                end: address,
+            });
+        };
+        let start = parse_address(start)?;
+        let end = match end.strip_prefix('=') {
+            Some(end) => parse_address(end)?,
+            None => parse_address(end)?
+                .checked_sub(1)
+                .ok_or_else(|| anyhow!("address range “{range}” is empty"))?,
+        };
+        if start > end {
+            bail!("address range “{range}” is empty");
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn contains(&self, address: BFieldElement) -> bool {
+        (self.start..=self.end).contains(&address.value())
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+impl Display for AddressRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}..={}", self.start, self.end)
+        }
+    }
+}
+
+impl Display for WatchpointHit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let address = self.access.address;
+        if self.access.is_write {
+            let (old, new) = (self.old_value, self.new_value);
+            write!(f, "write to ram[{address}]: {old} → {new}")
+        } else {
+            write!(f, "read from ram[{address}]: {}", self.new_value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+    use triton_vm::prelude::bfe;
+
+    use super::*;
+
+    #[test]
+    fn parse_address_ranges() {
+        let_assert!(Ok(range) = AddressRange::parse("42"));
+        assert!(AddressRange { start: 42, end: 42 } == range);
+
+        let_assert!(Ok(range) = AddressRange::parse("40..50"));
+        assert!(AddressRange { start: 40, end: 49 } == range);
+
+        let_assert!(Ok(range) = AddressRange::parse("40..=50"));
+        assert!(AddressRange { start: 40, end: 50 } == range);
+
+        let_assert!(Ok(range) = AddressRange::parse("-1"));
+        assert!(range.contains(bfe!(-1)));
+
+        assert!(let Err(_) = AddressRange::parse("50..40"));
+        assert!(let Err(_) = AddressRange::parse("0..0"));
+        assert!(let Err(_) = AddressRange::parse("foo"));
+    }
+
+    #[test]
+    fn watchpoint_mode_determines_triggering_accesses() {
+        let addresses = AddressRange::parse("10..20").unwrap();
+        let read = RamAccess {
+            address: bfe!(15),
+            is_write: false,
+        };
+        let write = RamAccess {
+            is_write: true,
+            ..read
+        };
+        let far_away = RamAccess {
+            address: bfe!(20),
+            ..write
+        };
+
+        let watch = |mode| Watchpoint { addresses, mode };
+        assert!(watch(WatchMode::Read).is_triggered_by(read));
+        assert!(!watch(WatchMode::Read).is_triggered_by(write));
+        assert!(watch(WatchMode::Write).is_triggered_by(write));
+        assert!(!watch(WatchMode::Write).is_triggered_by(read));
+        assert!(watch(WatchMode::Access).is_triggered_by(read));
+        assert!(watch(WatchMode::Access).is_triggered_by(write));
+        assert!(!watch(WatchMode::Access).is_triggered_by(far_away));
+    }
+}